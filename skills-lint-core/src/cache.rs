@@ -1,88 +1,488 @@
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 use crate::errors::LintError;
 use crate::tokenizer;
+use crate::types::FileResult;
 
-const CACHE_DIR: &str = ".skills-lint-cache";
+/// Default cache directory name, created under the config root.
+pub const CACHE_DIR: &str = ".skills-lint-cache";
+/// `cacheLocation` value selecting the cache shared by all projects of the current user.
+pub const USER_CACHE_LOCATION: &str = "user";
 const CACHE_FILE: &str = "tokens.json";
-const CACHE_VERSION: u64 = 1;
+/// Advisory lock file held while the cache file is read for merging and replaced.
+const LOCK_FILE: &str = "tokens.json.lock";
+const CACHE_VERSION: u64 = 3;
+
+/// Storage for token counts and per-file rule results, injected into the linter.
+///
+/// [`TokenCache`] persists to a cache file and [`MemoryCache`] lives in memory only.
+pub trait Cache {
+    /// Count tokens for `text` with `encoding`, reusing a stored count when possible.
+    fn count_tokens(&mut self, text: &str, encoding: &str) -> Result<usize, LintError>;
+
+    /// Look up the per-file rule results stored under `key`.
+    fn get_result(&mut self, key: &str) -> Option<FileResult>;
+
+    /// Store the per-file rule results for `key`.
+    fn insert_result(&mut self, key: String, result: FileResult);
+}
+
+/// A cache kept in memory for the lifetime of the value, e.g. for a long-running service.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCache {
+    counts: HashMap<String, usize>,
+    results: HashMap<String, FileResult>,
+}
+
+impl Cache for MemoryCache {
+    fn count_tokens(&mut self, text: &str, encoding: &str) -> Result<usize, LintError> {
+        let key = cache_key(text, encoding);
+        if let Some(&count) = self.counts.get(&key) {
+            return Ok(count);
+        }
+        let count = tokenizer::count_tokens(text, encoding)?;
+        self.counts.insert(key, count);
+        Ok(count)
+    }
+
+    fn get_result(&mut self, key: &str) -> Option<FileResult> {
+        self.results.get(key).cloned()
+    }
+
+    fn insert_result(&mut self, key: String, result: FileResult) {
+        self.results.insert(key, result);
+    }
+}
+
+/// How a run uses the cache file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Reuse cached entries and write new ones back.
+    #[default]
+    ReadWrite,
+    /// Reuse cached entries but never write the file.
+    ReadOnly,
+    /// Ignore existing entries and replace the file with the entries of this run.
+    WriteOnly,
+}
+
+/// A cached token count and the run that last used it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    tokens: usize,
+    last_used: u64,
+}
+
+/// The cached results of the per-file rules for one file and the run that last used them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResultEntry {
+    result: FileResult,
+    last_used: u64,
+    /// Keys of the token counts the result was computed from, marked as used along with it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    counts: Vec<String>,
+}
+
+/// Cache entries that record the run they were last used in.
+trait LastUsed {
+    fn last_used(&self) -> u64;
+    fn set_last_used(&mut self, run: u64);
+}
+
+impl LastUsed for Entry {
+    fn last_used(&self) -> u64 {
+        self.last_used
+    }
+
+    fn set_last_used(&mut self, run: u64) {
+        self.last_used = run;
+    }
+}
+
+impl LastUsed for ResultEntry {
+    fn last_used(&self) -> u64 {
+        self.last_used
+    }
+
+    fn set_last_used(&mut self, run: u64) {
+        self.last_used = run;
+    }
+}
+
+/// Cache hits and misses of one run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl RunStats {
+    /// Share of lookups served from the cache, or `None` if there were none.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+/// On-disk layout of the cache file.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheFile {
+    v: u64,
+    /// Number of runs that used the cache.
+    runs: u64,
+    last_run: RunStats,
+    entries: BTreeMap<String, Entry>,
+    /// Per-file rule results. Absent in files written before results were cached.
+    #[serde(default)]
+    results: BTreeMap<String, ResultEntry>,
+}
+
+/// Summary of a cache file, shown by `skills-lint cache stats`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub path: PathBuf,
+    pub entries: usize,
+    /// Number of cached per-file results.
+    pub results: usize,
+    /// Size of the cache file in bytes, or 0 if it does not exist.
+    pub size_bytes: u64,
+    pub runs: u64,
+    pub last_run: RunStats,
+}
 
 pub struct TokenCache {
-    entries: HashMap<String, usize>,
-    dirty: bool,
+    dir: PathBuf,
+    mode: CacheMode,
+    entries: BTreeMap<String, Entry>,
+    results: BTreeMap<String, ResultEntry>,
+    /// Runs recorded in the loaded file. This run is number `runs + 1`.
+    runs: u64,
+    last_run: RunStats,
+    /// Lookups made since the cache was loaded.
+    current: RunStats,
+    /// Digest of the file as loaded, to detect writes by other runs.
+    loaded: Option<String>,
+    /// Keys of entries and results removed by `prune`, so merging does not bring them back.
+    removed: HashSet<String>,
+    /// Keys of the counts looked up since a result lookup missed, while its result is
+    /// computed. These lookups are part of the result's miss and not counted on their own.
+    pending: Option<Vec<String>>,
 }
 
 impl TokenCache {
-    /// Load cache from disk. Returns an empty cache if the file is missing, corrupt, or wrong version.
-    pub fn load() -> Self {
-        let path = Path::new(CACHE_DIR).join(CACHE_FILE);
-        let entries = (|| -> Option<HashMap<String, usize>> {
-            let content = std::fs::read_to_string(&path).ok()?;
-            let parsed: serde_json::Value = serde_json::from_str(&content).ok()?;
-            let obj = parsed.as_object()?;
-            if obj.get("v")?.as_u64()? != CACHE_VERSION {
-                return None;
-            }
-            let entries_obj = obj.get("entries")?.as_object()?;
-            let mut map = HashMap::new();
-            for (k, v) in entries_obj {
-                map.insert(k.clone(), v.as_u64()? as usize);
-            }
-            Some(map)
-        })()
-        .unwrap_or_default();
+    /// Load cache from `dir`. Returns an empty cache if the file is missing, corrupt, or wrong version.
+    ///
+    /// Files from older versions keep their run statistics but start without entries, since
+    /// their keys lack the tokenizer fingerprint and cannot be trusted.
+    pub fn load(dir: &Path) -> Self {
+        Self::open(dir, CacheMode::ReadWrite)
+    }
+
+    /// Load the cache from `dir` for use in the given mode. In write-only mode, nothing is read.
+    pub fn open(dir: &Path, mode: CacheMode) -> Self {
+        let content = match mode {
+            CacheMode::WriteOnly => None,
+            CacheMode::ReadWrite | CacheMode::ReadOnly => std::fs::read_to_string(dir.join(CACHE_FILE)).ok(),
+        };
+        let loaded = content.as_deref().map(digest);
+        let (entries, results, runs, last_run) = match content.as_deref().and_then(parse) {
+            Some(file) => (file.entries, file.results, file.runs, file.last_run),
+            None => Default::default(),
+        };
 
         TokenCache {
+            dir: dir.to_path_buf(),
+            mode,
+            entries,
+            results,
+            runs,
+            last_run,
+            current: RunStats::default(),
+            loaded,
+            removed: HashSet::new(),
+            pending: None,
+        }
+    }
+
+    /// Path of the cache file.
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(CACHE_FILE)
+    }
+
+    /// Whether this run looked anything up, and so counts as a run.
+    fn used(&self) -> bool {
+        self.current.hits + self.current.misses > 0
+    }
+
+    /// Entry count, file size and the hit rate of the last recorded run.
+    pub fn stats(&self) -> CacheStats {
+        let path = self.path();
+        let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let (runs, last_run) = if self.used() {
+            (self.runs + 1, self.current)
+        } else {
+            (self.runs, self.last_run)
+        };
+        CacheStats {
+            path,
+            entries: self.entries.len(),
+            results: self.results.len(),
+            size_bytes,
+            runs,
+            last_run,
+        }
+    }
+
+    /// Remove token counts and results not used in the last `runs` runs, counting this run
+    /// if it used the cache. Returns the number of removed counts and results together.
+    ///
+    /// After linting the current files, `prune(1)` keeps only the entries they needed.
+    pub fn prune(&mut self, runs: u64) -> usize {
+        let latest = if self.used() { self.runs + 1 } else { self.runs };
+        let mut stale = prune_map(&mut self.entries, latest, runs);
+        stale.extend(prune_map(&mut self.results, latest, runs));
+        let removed = stale.len();
+        self.removed.extend(stale);
+        removed
+    }
+
+    /// Write the cache to disk if this run used or changed it. Does nothing in read-only mode.
+    ///
+    /// Safe to call from concurrent runs: an advisory lock serializes writers, entries
+    /// written by other runs since this cache was loaded are merged in rather than
+    /// overwritten, and the file is replaced atomically so readers never see a partial write.
+    /// In write-only mode the file is replaced without merging.
+    pub fn flush(&self) -> Result<(), LintError> {
+        if self.mode == CacheMode::ReadOnly || (!self.used() && self.removed.is_empty()) {
+            return Ok(());
+        }
+        let path = self.path();
+        let write_err = |e| LintError::CacheWrite(path.display().to_string(), e);
+        std::fs::create_dir_all(&self.dir).map_err(write_err)?;
+        let _lock = lock(&self.dir.join(LOCK_FILE)).map_err(write_err)?;
+
+        let on_disk = match self.mode {
+            CacheMode::WriteOnly => None,
+            CacheMode::ReadWrite | CacheMode::ReadOnly => std::fs::read_to_string(&path).ok(),
+        };
+        let doc = match on_disk.as_deref() {
+            Some(content) if Some(digest(content)) != self.loaded => match parse(content) {
+                Some(theirs) => self.merged_with(theirs),
+                None => self.to_file(self.runs),
+            },
+            _ => self.to_file(self.runs),
+        };
+
+        let json = serde_json::to_string_pretty(&doc).expect("failed to serialize cache");
+        let tmp = self.dir.join(format!("{CACHE_FILE}.{}.tmp", std::process::id()));
+        let result = File::create(&tmp)
+            .and_then(|mut file| {
+                file.write_all(json.as_bytes())?;
+                file.write_all(b"\n")?;
+                file.sync_all()
+            })
+            .and_then(|()| std::fs::rename(&tmp, &path));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+        result.map_err(write_err)
+    }
+
+    /// The cache file to write when the last recorded run on disk is `runs`.
+    fn to_file(&self, runs: u64) -> CacheFile {
+        let mut entries = self.entries.clone();
+        let mut results = self.results.clone();
+        let (runs, last_run) = if self.used() {
+            // Renumber this run's entries in case other runs finished since loading.
+            renumber(&mut entries, self.runs + 1, runs + 1);
+            renumber(&mut results, self.runs + 1, runs + 1);
+            (runs + 1, self.current)
+        } else {
+            (runs, self.last_run)
+        };
+        CacheFile {
+            v: CACHE_VERSION,
+            runs,
+            last_run,
             entries,
-            dirty: false,
+            results,
+        }
+    }
+
+    /// Combine this cache with a file written by another run since this one was loaded.
+    fn merged_with(&self, theirs: CacheFile) -> CacheFile {
+        let mut doc = self.to_file(self.runs.max(theirs.runs));
+        if !self.used() {
+            doc.last_run = theirs.last_run;
+        }
+        merge_map(&mut doc.entries, theirs.entries, &self.removed);
+        merge_map(&mut doc.results, theirs.results, &self.removed);
+        doc
+    }
+
+    /// Delete the cache file in `dir`. Returns whether a cache file existed.
+    ///
+    /// The lock file is left in place: deleting it would let a run still waiting on it and
+    /// a run creating a new one both take "the" lock.
+    pub fn clear(dir: &Path) -> Result<bool, LintError> {
+        let path = dir.join(CACHE_FILE);
+        if !path.exists() {
+            return Ok(false);
+        }
+        let write_err = |e| LintError::CacheWrite(path.display().to_string(), e);
+        let _lock = lock(&dir.join(LOCK_FILE)).map_err(write_err)?;
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(write_err(e)),
         }
     }
+}
 
-    /// Count tokens for the given text and encoding, using the cache when possible.
-    pub fn count_tokens(&mut self, text: &str, encoding: &str) -> Result<usize, LintError> {
+impl Cache for TokenCache {
+    fn count_tokens(&mut self, text: &str, encoding: &str) -> Result<usize, LintError> {
         let key = cache_key(text, encoding);
-        if let Some(&count) = self.entries.get(&key) {
-            return Ok(count);
+        let run = self.runs + 1;
+        let (count, hit) = match self.entries.get_mut(&key) {
+            Some(entry) => {
+                entry.last_used = run;
+                (entry.tokens, true)
+            }
+            None => {
+                let count = tokenizer::count_tokens(text, encoding)?;
+                self.entries.insert(key.clone(), Entry { tokens: count, last_used: run });
+                (count, false)
+            }
+        };
+        match self.pending {
+            Some(ref mut pending) => pending.push(key),
+            None if hit => self.current.hits += 1,
+            None => self.current.misses += 1,
         }
-        let count = tokenizer::count_tokens(text, encoding)?;
-        self.entries.insert(key, count);
-        self.dirty = true;
         Ok(count)
     }
 
-    /// Write cache to disk if dirty. Errors are silently ignored.
-    pub fn flush(&self) {
-        if !self.dirty {
-            return;
+    fn get_result(&mut self, key: &str) -> Option<FileResult> {
+        let run = self.runs + 1;
+        self.pending = None;
+        let Some(entry) = self.results.get_mut(key) else {
+            self.current.misses += 1;
+            self.pending = Some(Vec::new());
+            return None;
+        };
+        entry.last_used = run;
+        self.current.hits += 1;
+        // The counts behind a result stay needed for `--cost` and budget changes.
+        for count in &entry.counts {
+            if let Some(count) = self.entries.get_mut(count) {
+                count.last_used = run;
+            }
+        }
+        Some(entry.result.clone())
+    }
+
+    fn insert_result(&mut self, key: String, result: FileResult) {
+        let last_used = self.runs + 1;
+        let counts = self.pending.take().unwrap_or_default();
+        self.results.insert(key, ResultEntry { result, last_used, counts });
+    }
+}
+
+/// Remove entries of `map` not used in the last `runs` runs up to `latest`, returning their keys.
+fn prune_map<T: LastUsed>(map: &mut BTreeMap<String, T>, latest: u64, runs: u64) -> Vec<String> {
+    let stale: Vec<String> = map
+        .iter()
+        .filter(|(_, entry)| latest.saturating_sub(entry.last_used()) >= runs)
+        .map(|(key, _)| key.clone())
+        .collect();
+    for key in &stale {
+        map.remove(key);
+    }
+    stale
+}
+
+fn renumber<T: LastUsed>(map: &mut BTreeMap<String, T>, from: u64, to: u64) {
+    for entry in map.values_mut().filter(|entry| entry.last_used() == from) {
+        entry.set_last_used(to);
+    }
+}
+
+/// Add entries written by another run, except those this run pruned.
+fn merge_map<T: LastUsed>(ours: &mut BTreeMap<String, T>, theirs: BTreeMap<String, T>, removed: &HashSet<String>) {
+    for (key, entry) in theirs {
+        if removed.contains(&key) {
+            continue;
+        }
+        match ours.get_mut(&key) {
+            Some(existing) => existing.set_last_used(existing.last_used().max(entry.last_used())),
+            None => {
+                ours.insert(key, entry);
+            }
         }
-        let _ = (|| -> Result<(), Box<dyn std::error::Error>> {
-            std::fs::create_dir_all(CACHE_DIR)?;
-            let entries: serde_json::Value = self
-                .entries
-                .iter()
-                .map(|(k, &v)| (k.clone(), serde_json::Value::from(v)))
-                .collect::<serde_json::Map<String, serde_json::Value>>()
-                .into();
-            let doc = serde_json::json!({
-                "v": CACHE_VERSION,
-                "entries": entries,
-            });
-            let json = serde_json::to_string_pretty(&doc)?;
-            std::fs::write(Path::new(CACHE_DIR).join(CACHE_FILE), format!("{json}\n"))?;
-            Ok(())
-        })();
     }
 }
 
+/// Open `path` and take an exclusive advisory lock on it, released when the file is dropped.
+fn lock(path: &Path) -> std::io::Result<File> {
+    let file = OpenOptions::new().create(true).truncate(false).write(true).open(path)?;
+    file.lock()?;
+    Ok(file)
+}
+
+fn digest(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// Parse a cache file, migrating older versions. Returns `None` for unknown versions.
+fn parse(content: &str) -> Option<CacheFile> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    let counter = |key: &str| value.get(key).and_then(serde_json::Value::as_u64).unwrap_or(0);
+    match value.get("v")?.as_u64()? {
+        // v1 and v2 keys used a truncated hash and no tokenizer fingerprint.
+        1 | 2 => Some(CacheFile {
+            v: CACHE_VERSION,
+            runs: counter("runs"),
+            last_run: value
+                .get("lastRun")
+                .and_then(|stats| serde_json::from_value(stats.clone()).ok())
+                .unwrap_or_default(),
+            entries: BTreeMap::new(),
+            results: BTreeMap::new(),
+        }),
+        CACHE_VERSION => serde_json::from_value(value).ok(),
+        _ => None,
+    }
+}
+
+/// The per-user cache directory: `$XDG_CACHE_HOME/skills-lint`, or `~/.cache/skills-lint`
+/// when `XDG_CACHE_HOME` is unset. Returns `None` if neither location is known.
+pub fn user_cache_dir() -> Option<PathBuf> {
+    user_cache_dir_with(&|name| std::env::var_os(name).map(PathBuf::from))
+}
+
+fn user_cache_dir_with(lookup: &dyn Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+    // The XDG spec says relative paths are invalid and must be ignored.
+    let base = lookup("XDG_CACHE_HOME")
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| lookup("HOME").filter(|home| !home.as_os_str().is_empty()).map(|home| home.join(".cache")))
+        .or_else(|| lookup("LOCALAPPDATA"))?;
+    Some(base.join("skills-lint"))
+}
+
+/// Key of a count: the full SHA-256 of the text, the encoding and the tokenizer fingerprint.
 fn cache_key(text: &str, encoding: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(text.as_bytes());
-    let hash = hasher.finalize();
-    let hex = &format!("{hash:x}")[..16];
-    format!("{hex}:{encoding}")
+    let hash = Sha256::digest(text.as_bytes());
+    format!("{hash:x}:{encoding}:{}", tokenizer::fingerprint(encoding))
 }
 
 #[cfg(test)]
@@ -92,30 +492,181 @@ mod tests {
     #[test]
     fn test_cache_key_format() {
         let key = cache_key("hello", "cl100k_base");
-        assert!(key.ends_with(":cl100k_base"));
-        // 16 hex chars + colon + encoding name
-        let parts: Vec<&str> = key.splitn(2, ':').collect();
-        assert_eq!(parts[0].len(), 16);
+        // 64 hex chars, encoding name and tokenizer fingerprint
+        let parts: Vec<&str> = key.split(':').collect();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].len(), 64);
         assert_eq!(parts[1], "cl100k_base");
+        assert_eq!(parts[2], tokenizer::fingerprint("cl100k_base"));
+    }
+
+    fn empty() -> TokenCache {
+        TokenCache {
+            dir: PathBuf::from(CACHE_DIR),
+            mode: CacheMode::ReadWrite,
+            entries: BTreeMap::new(),
+            results: BTreeMap::new(),
+            runs: 0,
+            last_run: RunStats::default(),
+            current: RunStats::default(),
+            loaded: None,
+            removed: HashSet::new(),
+            pending: None,
+        }
     }
 
     #[test]
     fn test_cache_hit() {
-        let mut cache = TokenCache {
-            entries: HashMap::new(),
-            dirty: false,
-        };
+        let mut cache = empty();
         let count1 = cache.count_tokens("Hello, world!", "cl100k_base").unwrap();
         assert!(!cache.entries.is_empty());
-        assert!(cache.dirty);
         let count2 = cache.count_tokens("Hello, world!", "cl100k_base").unwrap();
         assert_eq!(count1, count2);
+        assert_eq!(cache.current, RunStats { hits: 1, misses: 1 });
+        assert_eq!(cache.stats().runs, 1);
+        assert_eq!(cache.stats().last_run.hit_rate(), Some(0.5));
+    }
+
+    #[test]
+    fn test_parse_migrates_old_versions() {
+        let file = parse(r#"{ "v": 1, "entries": { "abc:cl100k_base": 42 } }"#).unwrap();
+        assert_eq!(file.v, CACHE_VERSION);
+        assert_eq!(file.runs, 0);
+        assert!(file.entries.is_empty());
+
+        let v2 = r#"{ "v": 2, "runs": 7, "lastRun": { "hits": 3, "misses": 1 },
+            "entries": { "abc:cl100k_base": { "tokens": 42, "lastUsed": 7 } } }"#;
+        let file = parse(v2).unwrap();
+        assert_eq!(file.runs, 7);
+        assert_eq!(file.last_run, RunStats { hits: 3, misses: 1 });
+        assert!(file.entries.is_empty());
+        assert!(parse(r#"{ "v": 99, "entries": {} }"#).is_none());
+        assert!(parse("not json").is_none());
+    }
+
+    #[test]
+    fn test_prune_by_last_used_run() {
+        let mut cache = empty();
+        cache.runs = 10;
+        for (key, last_used) in [("old", 2), ("recent", 8), ("latest", 10)] {
+            cache.entries.insert(key.to_string(), Entry { tokens: 1, last_used });
+        }
+        assert_eq!(cache.prune(3), 1);
+        assert!(cache.removed.contains("old"));
+        assert!(!cache.entries.contains_key("old"));
+
+        // Once this run used the cache, only its own lookups survive `prune(1)`.
+        cache.count_tokens("Hello", "cl100k_base").unwrap();
+        assert_eq!(cache.prune(1), 2);
+        assert_eq!(cache.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_result_hit_keeps_its_counts() {
+        let mut cache = empty();
+        assert_eq!(cache.get_result("file"), None);
+        cache.count_tokens("body", "cl100k_base").unwrap();
+        cache.count_tokens("body", "o200k_base").unwrap();
+        cache.insert_result("file".to_string(), FileResult::default());
+        cache.count_tokens("index", "cl100k_base").unwrap();
+        // The result miss stands for the counts made to compute it.
+        assert_eq!(cache.current, RunStats { hits: 0, misses: 2 });
+        assert_eq!(cache.results["file"].counts.len(), 2);
+
+        // A later run that only hits the result still uses the counts behind it.
+        cache.runs += 1;
+        cache.current = RunStats::default();
+        assert!(cache.get_result("file").is_some());
+        assert_eq!(cache.prune(1), 1);
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.current, RunStats { hits: 1, misses: 0 });
+    }
+
+    #[test]
+    fn test_flush_merges_concurrent_runs() {
+        let dir = std::env::temp_dir().join(format!("skills-lint-cache-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut first = TokenCache::load(&dir);
+        let mut second = TokenCache::load(&dir);
+        first.count_tokens("first", "cl100k_base").unwrap();
+        second.count_tokens("second", "cl100k_base").unwrap();
+        first.flush().unwrap();
+        second.flush().unwrap();
+
+        let merged = TokenCache::load(&dir);
+        assert_eq!(merged.entries.len(), 2);
+        assert_eq!(merged.runs, 2);
+        assert_eq!(merged.entries[&cache_key("second", "cl100k_base")].last_used, 2);
+
+        // A prune that races with another run keeps the other run's new entries only.
+        let mut pruner = TokenCache::load(&dir);
+        let mut other = TokenCache::load(&dir);
+        other.count_tokens("third", "cl100k_base").unwrap();
+        assert_eq!(pruner.prune(1), 1);
+        other.flush().unwrap();
+        pruner.flush().unwrap();
+        let keys: HashSet<String> = TokenCache::load(&dir).entries.into_keys().collect();
+        assert_eq!(keys, HashSet::from([cache_key("second", "cl100k_base"), cache_key("third", "cl100k_base")]));
+
+        assert!(TokenCache::clear(&dir).unwrap());
+        assert!(!dir.join(CACHE_FILE).exists());
+        assert!(dir.join(LOCK_FILE).exists());
+        assert!(!TokenCache::clear(&dir).unwrap());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_read_only_and_write_only_modes() {
+        let dir = std::env::temp_dir().join(format!("skills-lint-cache-mode-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut seed = TokenCache::load(&dir);
+        seed.count_tokens("old", "cl100k_base").unwrap();
+        seed.flush().unwrap();
+        let before = std::fs::read_to_string(dir.join(CACHE_FILE)).unwrap();
+
+        let mut read_only = TokenCache::open(&dir, CacheMode::ReadOnly);
+        read_only.count_tokens("old", "cl100k_base").unwrap();
+        read_only.count_tokens("new", "cl100k_base").unwrap();
+        assert_eq!(read_only.current, RunStats { hits: 1, misses: 1 });
+        read_only.flush().unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join(CACHE_FILE)).unwrap(), before);
+
+        let mut write_only = TokenCache::open(&dir, CacheMode::WriteOnly);
+        assert!(write_only.entries.is_empty());
+        write_only.count_tokens("new", "cl100k_base").unwrap();
+        write_only.flush().unwrap();
+        let keys: Vec<String> = TokenCache::load(&dir).entries.into_keys().collect();
+        assert_eq!(keys, vec![cache_key("new", "cl100k_base")]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_user_cache_dir() {
+        let xdg = |name: &str| match name {
+            "XDG_CACHE_HOME" => Some(PathBuf::from("/xdg")),
+            "HOME" => Some(PathBuf::from("/home/me")),
+            _ => None,
+        };
+        assert_eq!(user_cache_dir_with(&xdg), Some(PathBuf::from("/xdg/skills-lint")));
+
+        let relative_xdg = |name: &str| match name {
+            "XDG_CACHE_HOME" => Some(PathBuf::from("cache")),
+            "HOME" => Some(PathBuf::from("/home/me")),
+            _ => None,
+        };
+        assert_eq!(user_cache_dir_with(&relative_xdg), Some(PathBuf::from("/home/me/.cache/skills-lint")));
+        assert_eq!(user_cache_dir_with(&|_| None), None);
     }
 
     #[test]
     fn test_load_empty() {
-        let cache = TokenCache::load();
+        let cache = TokenCache::load(Path::new("nonexistent_dir_xyz"));
         // Should not panic, just return empty
-        assert!(!cache.dirty);
+        assert!(cache.entries.is_empty());
+        assert_eq!(cache.stats().entries, 0);
+        assert_eq!(cache.stats().size_bytes, 0);
     }
 }
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::de::{self, Deserializer, Visitor};
+use serde::Deserialize;
+use serde_json::json;
+
+/// A warning or error threshold: an absolute token count, or a percentage of the
+/// model's max input size written as a string such as `"5%"`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    Tokens(usize),
+    Percent(f64),
+}
+
+impl Budget {
+    /// Resolve the budget to a token count for a model with the given max input size.
+    pub fn resolve(self, max_input: usize) -> usize {
+        match self {
+            Budget::Tokens(tokens) => tokens,
+            Budget::Percent(percent) => (max_input as f64 * percent / 100.0).round() as usize,
+        }
+    }
+
+    fn parse_percent(text: &str) -> Result<Self, String> {
+        let number = text
+            .trim()
+            .strip_suffix('%')
+            .ok_or_else(|| format!("expected a token count or a percentage such as \"5%\", got \"{text}\""))?;
+        let percent: f64 = number
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid percentage \"{text}\""))?;
+        if !(percent > 0.0 && percent <= 100.0) {
+            return Err(format!("percentage \"{text}\" must be greater than 0% and at most 100%"));
+        }
+        Ok(Budget::Percent(percent))
+    }
+}
+
+impl From<usize> for Budget {
+    fn from(tokens: usize) -> Self {
+        Budget::Tokens(tokens)
+    }
+}
+
+impl fmt::Display for Budget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Budget::Tokens(tokens) => write!(f, "{tokens}"),
+            Budget::Percent(percent) => write!(f, "{percent}%"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Budget {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BudgetVisitor;
+
+        impl Visitor<'_> for BudgetVisitor {
+            type Value = Budget;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a token count or a percentage such as \"5%\"")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Budget, E> {
+                usize::try_from(value)
+                    .map(Budget::Tokens)
+                    .map_err(|_| E::custom(format!("token count {value} is too large")))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Budget, E> {
+                u64::try_from(value)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+                    .and_then(|value| self.visit_u64(value))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Budget, E> {
+                Budget::parse_percent(value).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(BudgetVisitor)
+    }
+}
+
+impl JsonSchema for Budget {
+    fn schema_name() -> String {
+        "Budget".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        serde_json::from_value(json!({
+            "description": "Token count, or a percentage of the model's max input such as \"5%\".",
+            "anyOf": [
+                { "type": "integer", "minimum": 0 },
+                { "type": "string", "pattern": "^\\s*[0-9]+(\\.[0-9]+)?\\s*%\\s*$" }
+            ]
+        }))
+        .expect("valid schema")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_tokens_and_percent() {
+        let budgets: Vec<Budget> = serde_json::from_str(r#"[8000, "5%", "2.5 %"]"#).unwrap();
+        assert_eq!(budgets, vec![Budget::Tokens(8000), Budget::Percent(5.0), Budget::Percent(2.5)]);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_invalid() {
+        for invalid in [r#""5""#, r#""abc%""#, r#""0%""#, r#""150%""#, "-1", "1.5"] {
+            assert!(serde_json::from_str::<Budget>(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(Budget::Tokens(8000).resolve(111_616), 8000);
+        assert_eq!(Budget::Percent(5.0).resolve(111_616), 5581);
+        assert_eq!(Budget::Percent(10.0).resolve(272_000), 27_200);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Budget::Tokens(8000).to_string(), "8000");
+        assert_eq!(Budget::Percent(2.5).to_string(), "2.5%");
+    }
+}
@@ -0,0 +1,140 @@
+use serde::Serialize;
+
+use crate::config::{supported_model_names, Config, ResolvedBudget, SizeMetric};
+
+/// One rule setting that applies to a file: a budget for a rule × model pair, a size
+/// budget (with the metric in `model`), or a structural rule that is enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EffectiveRule {
+    pub rule: &'static str,
+    pub model: Option<String>,
+    pub encoding: Option<String>,
+    pub warning: Option<usize>,
+    pub error: Option<usize>,
+    /// Index into `overrides` of the entry that last changed this budget, if any.
+    #[serde(rename = "override")]
+    pub override_index: Option<usize>,
+}
+
+impl EffectiveRule {
+    fn budget(rule: &'static str, model: &str, budget: ResolvedBudget) -> Self {
+        Self {
+            rule,
+            model: Some(model.to_string()),
+            encoding: Some(budget.encoding),
+            warning: Some(budget.warning),
+            error: Some(budget.error),
+            override_index: budget.override_index,
+        }
+    }
+
+    fn enabled(rule: &'static str) -> Self {
+        Self {
+            rule,
+            model: None,
+            encoding: None,
+            warning: None,
+            error: None,
+            override_index: None,
+        }
+    }
+}
+
+/// Resolve every enabled rule for a root-relative `file`, using the same resolvers as the linter.
+///
+/// Budgets are listed per rule in the order of the supported model registry, followed by
+/// size budgets and the enabled structural rules.
+pub fn effective_rules(config: &Config, file: &str) -> Vec<EffectiveRule> {
+    let models = supported_model_names();
+    let mut rules = Vec::new();
+
+    for &model in &models {
+        if let Some(budget) = config.resolve_token_limit(file, model) {
+            rules.push(EffectiveRule::budget("token-limit", model, budget));
+        }
+    }
+    for &model in &models {
+        if let Some(budget) = config.resolve_frontmatter_limit(model) {
+            rules.push(EffectiveRule::budget("frontmatter-limit", model, budget));
+        }
+    }
+    for &model in &models {
+        if let Some(budget) = config.resolve_skill_index_budget(model) {
+            rules.push(EffectiveRule::budget("skill-index-budget", model, budget));
+        }
+    }
+
+    for metric in SizeMetric::ALL {
+        if let Some(budget) = config.resolve_size_limit(file, metric) {
+            rules.push(EffectiveRule {
+                rule: "size-limit",
+                model: Some(metric.label().to_string()),
+                encoding: None,
+                warning: Some(budget.warning),
+                error: Some(budget.error),
+                override_index: budget.override_index,
+            });
+        }
+    }
+
+    for (rule, enabled) in [
+        ("skill-structure", config.rules.skill_structure),
+        ("unique-name", config.rules.unique_name),
+        ("unique-description", config.rules.unique_description),
+    ] {
+        if enabled == Some(true) {
+            rules.push(EffectiveRule::enabled(rule));
+        }
+    }
+
+    rules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_rules_report_winning_override() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4": { "warning": 2000, "error": 4000 },
+                        "gpt-4o": { "warning": 8000, "error": 16000 }
+                    }
+                },
+                "frontmatter-limit": { "models": { "gpt-4o": { "warning": 1000, "error": 2000 } } },
+                "skill-structure": true,
+                "unique-name": false
+            },
+            "overrides": [
+                { "files": ["*.md"], "rules": { "token-limit": { "models": { "gpt-4o": { "warning": 9000 } } } } },
+                { "files": ["big.md"], "rules": { "token-limit": { "models": { "gpt-4o": { "error": 32000 } } } } }
+            ]
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let rules = effective_rules(&config, "big.md");
+
+        let names: Vec<(&str, Option<&str>)> = rules.iter().map(|r| (r.rule, r.model.as_deref())).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("token-limit", Some("gpt-4o")),
+                ("token-limit", Some("gpt-4")),
+                ("frontmatter-limit", Some("gpt-4o")),
+                ("skill-structure", None),
+            ]
+        );
+
+        assert_eq!(rules[0].warning, Some(9000));
+        assert_eq!(rules[0].error, Some(32000));
+        assert_eq!(rules[0].override_index, Some(1));
+        assert_eq!(rules[1].override_index, None);
+
+        let other = effective_rules(&config, "small.md");
+        assert_eq!(other[0].error, Some(16000));
+        assert_eq!(other[0].override_index, Some(0));
+    }
+}
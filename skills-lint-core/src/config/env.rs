@@ -0,0 +1,204 @@
+use serde_json::Value;
+
+use crate::errors::ConfigIssue;
+
+/// Replace `${VAR}` and `${VAR:-default}` references in every string value of a raw config.
+///
+/// Keys are left untouched. In fields that take a number or boolean, such as budgets,
+/// prices, `cache` and `respectIgnoreFiles`, a string that consists of a single reference
+/// is converted when the substituted text is one. `$${` produces a literal `${`.
+pub fn interpolate(value: &mut Value) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    interpolate_with(value, &|name| std::env::var(name).ok(), &mut Vec::new(), &mut issues);
+    issues
+}
+
+fn interpolate_with(
+    value: &mut Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    path: &mut Vec<String>,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    match value {
+        Value::String(text) if text.contains("${") => match substitute(text, lookup) {
+            Ok(result) => *value = coerce(path, text, result),
+            Err(message) => issues.push(ConfigIssue::new(path.clone(), message)),
+        },
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                path.push(i.to_string());
+                interpolate_with(item, lookup, path, issues);
+                path.pop();
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                path.push(key.clone());
+                interpolate_with(item, lookup, path, issues);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Expand every reference in `text`.
+fn substitute(text: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("$${") {
+            out.push_str("${");
+            rest = after;
+            continue;
+        }
+        let Some(expr) = rest.strip_prefix("${") else {
+            out.push('$');
+            rest = &rest[1..];
+            continue;
+        };
+        let end = expr
+            .find('}')
+            .ok_or_else(|| format!("unterminated \"${{\" in \"{text}\""))?;
+        let (name, default) = match expr[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&expr[..end], None),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("invalid environment variable name \"{name}\" in \"{text}\""));
+        }
+        match (lookup(name).filter(|v| !v.is_empty() || default.is_none()), default) {
+            (Some(v), _) => out.push_str(&v),
+            (None, Some(default)) => out.push_str(default),
+            (None, None) => return Err(format!("environment variable \"{name}\" is not set")),
+        }
+        rest = &expr[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Type a field at a config path takes, when it is not a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldType {
+    Integer,
+    Number,
+    Bool,
+}
+
+/// Return the non-string type of the field at `path`, or None for string fields.
+fn field_type(path: &[String]) -> Option<FieldType> {
+    let path: Vec<&str> = path.iter().map(String::as_str).collect();
+    match path.as_slice() {
+        ["cache" | "respectIgnoreFiles"] => Some(FieldType::Bool),
+        ["pricing", _, "input"] => Some(FieldType::Number),
+        ["rules", rule @ ..] | ["overrides", _, "rules", rule @ ..] => match rule {
+            ["skill-structure" | "unique-name" | "unique-description"] => Some(FieldType::Bool),
+            [_, "models", _, "warning" | "error"] | ["size-limit", _, "warning" | "error"] => Some(FieldType::Integer),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Convert the result to the type of the field at `path` if the original value was a
+/// single reference and the result parses as that type.
+fn coerce(path: &[String], original: &str, result: String) -> Value {
+    let single = original.starts_with("${") && original.ends_with('}') && original.matches("${").count() == 1;
+    let text = result.trim();
+    let coerced = match field_type(path).filter(|_| single) {
+        Some(FieldType::Integer) => text.parse::<u64>().ok().map(Value::from),
+        Some(FieldType::Number) => text.parse::<f64>().ok().filter(|n| n.is_finite()).map(Value::from),
+        Some(FieldType::Bool) => text.parse::<bool>().ok().map(Value::Bool),
+        None => None,
+    };
+    coerced.unwrap_or(Value::String(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(mut value: Value) -> (Value, Vec<ConfigIssue>) {
+        let lookup = |name: &str| match name {
+            "CONTEXT" => Some("12000".to_string()),
+            "SKILLS_DIR" => Some("skills".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        };
+        let mut issues = Vec::new();
+        interpolate_with(&mut value, &lookup, &mut Vec::new(), &mut issues);
+        (value, issues)
+    }
+
+    #[test]
+    fn test_interpolates_strings_and_coerces_numbers() {
+        let (value, issues) = run(json!({
+            "patterns": ["./${SKILLS_DIR}/**/SKILL.md"],
+            "rules": {
+                "token-limit": { "models": { "gpt-4o": {
+                    "warning": "${WARN:-8000}",
+                    "error": "${CONTEXT}",
+                    "encoding": "${ENCODING:-o200k_base}"
+                } } },
+                "unique-name": "${UNIQUE:-true}"
+            }
+        }));
+        assert!(issues.is_empty());
+        assert_eq!(value["patterns"][0], "./skills/**/SKILL.md");
+        let budget = &value["rules"]["token-limit"]["models"]["gpt-4o"];
+        assert_eq!(budget["warning"], 8000);
+        assert_eq!(budget["error"], 12000);
+        assert_eq!(budget["encoding"], "o200k_base");
+        assert_eq!(value["rules"]["unique-name"], true);
+    }
+
+    #[test]
+    fn test_default_applies_to_empty_variable() {
+        let (value, _) = run(json!({ "a": "${EMPTY:-x}", "b": "${EMPTY}" }));
+        assert_eq!(value["a"], "x");
+        assert_eq!(value["b"], "");
+    }
+
+    #[test]
+    fn test_escape_and_plain_dollar() {
+        let (value, issues) = run(json!({ "a": "$${CONTEXT} costs $5" }));
+        assert!(issues.is_empty());
+        assert_eq!(value["a"], "${CONTEXT} costs $5");
+    }
+
+    #[test]
+    fn test_missing_variable_reports_path() {
+        let (_, issues) = run(json!({ "rules": { "token-limit": { "models": { "gpt-4o": { "error": "${NOPE}" } } } } }));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, vec!["rules", "token-limit", "models", "gpt-4o", "error"]);
+        assert!(issues[0].message.contains("\"NOPE\" is not set"));
+    }
+
+    #[test]
+    fn test_string_fields_are_not_coerced() {
+        let (value, _) = run(json!({
+            "patterns": ["${CONTEXT}"],
+            "cacheLocation": "${CONTEXT}",
+            "cache": "${UNIQUE:-false}",
+            "pricing": { "gpt-4o": { "input": "${PRICE:-2.5}" } },
+            "overrides": [{ "files": ["${CONTEXT}"], "rules": { "size-limit": { "maxWords": { "error": "${CONTEXT}" } } } }]
+        }));
+        assert_eq!(value["patterns"][0], "12000");
+        assert_eq!(value["cacheLocation"], "12000");
+        assert_eq!(value["cache"], false);
+        assert_eq!(value["pricing"]["gpt-4o"]["input"], 2.5);
+        assert_eq!(value["overrides"][0]["files"][0], "12000");
+        assert_eq!(value["overrides"][0]["rules"]["size-limit"]["maxWords"]["error"], 12000);
+    }
+
+    #[test]
+    fn test_mixed_text_stays_string() {
+        let (value, _) = run(json!({ "cache": "${CONTEXT}0", "rules": { "gpt-4o": "v${CONTEXT}" } }));
+        assert_eq!(value["cache"], "120000");
+        assert_eq!(value["rules"]["gpt-4o"], "v12000");
+    }
+}
@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::config::env;
+use crate::config::format::parse_value;
+use crate::config::presets::{self, PRESET_NAMES, PRESET_PREFIX};
+use crate::config::validate;
+use crate::errors::LintError;
+
+/// Top-level key listing the configs a file extends.
+pub const EXTENDS_KEY: &str = "extends";
+
+/// A config file read as raw JSON with its `extends` chain resolved.
+#[derive(Debug, Clone)]
+pub struct LoadedValue {
+    /// The bases merged in order with the file on top.
+    pub value: Value,
+    /// The keys set by the file itself, without `extends`.
+    pub own: Value,
+    /// Text of the file, for locating keys in errors.
+    pub content: String,
+}
+
+/// Read the config at `path` as raw JSON and resolve its `extends` chain.
+///
+/// Environment variables are interpolated in each file as it is read. Bases are merged
+/// in order, then the file itself is merged on top.
+pub fn load_value(path: &Path) -> Result<LoadedValue, LintError> {
+    resolve(path, &mut Vec::new())
+}
+
+fn resolve(path: &Path, stack: &mut Vec<PathBuf>) -> Result<LoadedValue, LintError> {
+    let display = path.display().to_string();
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if stack.contains(&canonical) {
+        return Err(LintError::ConfigExtends(display, "circular extends".to_string()));
+    }
+    stack.push(canonical);
+
+    let content = std::fs::read_to_string(path).map_err(|e| LintError::ConfigRead(display.clone(), e))?;
+    let mut value = parse_value(path, &content)?;
+    let mut issues = validate::unknown_keys(&value);
+    if issues.is_empty() {
+        issues = env::interpolate(&mut value);
+    }
+    if !issues.is_empty() {
+        validate::attach_source(&mut issues, &display, &content);
+        return Err(LintError::ConfigInvalid(issues));
+    }
+    let extends = take_extends(&mut value, &display)?;
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut merged = Value::Object(Map::new());
+    for entry in &extends {
+        let base = match entry.strip_prefix(PRESET_PREFIX) {
+            Some(name) => preset(name, entry, &display)?,
+            None => resolve(&dir.join(entry), stack)?.value,
+        };
+        merge(&mut merged, base);
+    }
+    merge(&mut merged, value.clone());
+
+    stack.pop();
+    Ok(LoadedValue {
+        value: merged,
+        own: value,
+        content,
+    })
+}
+
+/// Resolve the `extends` chain of a config that has no file behind it.
+///
+/// Only presets can be extended, since relative paths have nothing to resolve against.
+pub fn resolve_inline(mut value: Value, display: &str) -> Result<Value, LintError> {
+    let issues = validate::unknown_keys(&value);
+    if !issues.is_empty() {
+        return Err(LintError::ConfigInvalid(issues));
+    }
+    let extends = take_extends(&mut value, display)?;
+
+    let mut merged = Value::Object(Map::new());
+    for entry in &extends {
+        let Some(name) = entry.strip_prefix(PRESET_PREFIX) else {
+            return Err(LintError::ConfigExtends(
+                display.to_string(),
+                format!("cannot extend '{entry}': a config without a file can only extend presets"),
+            ));
+        };
+        merge(&mut merged, preset(name, entry, display)?);
+    }
+    merge(&mut merged, value);
+    Ok(merged)
+}
+
+fn preset(name: &str, entry: &str, display: &str) -> Result<Value, LintError> {
+    presets::preset(name).ok_or_else(|| {
+        LintError::ConfigExtends(
+            display.to_string(),
+            format!("unknown preset '{entry}'. Available presets: {PRESET_NAMES:?}"),
+        )
+    })
+}
+
+/// Remove and return the `extends` entry, which may be a single string or a list of strings.
+fn take_extends(value: &mut Value, display: &str) -> Result<Vec<String>, LintError> {
+    let extends = match value.as_object_mut().and_then(|obj| obj.remove(EXTENDS_KEY)) {
+        None | Some(Value::Null) => return Ok(Vec::new()),
+        Some(extends) => extends,
+    };
+    let invalid = || LintError::ConfigExtends(display.to_string(), "expected a string or a list of strings".to_string());
+    match extends {
+        Value::String(entry) => Ok(vec![entry]),
+        Value::Array(entries) => entries
+            .into_iter()
+            .map(|entry| match entry {
+                Value::String(entry) => Ok(entry),
+                _ => Err(invalid()),
+            })
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+/// Deep-merge `overlay` into `base`.
+///
+/// Objects merge key by key, top-level `overrides` lists are concatenated, a `null`
+/// removes the inherited key, and any other value replaces the inherited one.
+pub fn merge(base: &mut Value, overlay: Value) {
+    merge_at(base, overlay, true);
+}
+
+fn merge_at(base: &mut Value, overlay: Value, top_level: bool) {
+    let Value::Object(overlay_map) = overlay else {
+        *base = overlay;
+        return;
+    };
+    let Value::Object(base_map) = &mut *base else {
+        *base = Value::Object(overlay_map);
+        return;
+    };
+
+    for (key, value) in overlay_map {
+        if value.is_null() {
+            base_map.remove(&key);
+            continue;
+        }
+        match base_map.get_mut(&key) {
+            Some(Value::Array(existing)) if top_level && key == "overrides" => {
+                if let Value::Array(items) = value {
+                    existing.extend(items);
+                } else {
+                    base_map.insert(key, value);
+                }
+            }
+            Some(existing) => merge_at(existing, value, false),
+            None => {
+                base_map.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_rules_and_models_deeply() {
+        let mut base = json!({
+            "patterns": ["a/**"],
+            "rules": {
+                "token-limit": { "models": { "gpt-4o": { "warning": 1, "error": 2 } } },
+                "skill-structure": true
+            }
+        });
+        merge(
+            &mut base,
+            json!({
+                "patterns": ["b/**"],
+                "rules": {
+                    "token-limit": { "models": { "gpt-4o": { "error": 3 }, "gpt-4": { "warning": 4, "error": 5 } } }
+                }
+            }),
+        );
+        assert_eq!(base["patterns"], json!(["b/**"]));
+        assert_eq!(base["rules"]["token-limit"]["models"]["gpt-4o"], json!({ "warning": 1, "error": 3 }));
+        assert_eq!(base["rules"]["token-limit"]["models"]["gpt-4"]["error"], 5);
+        assert_eq!(base["rules"]["skill-structure"], true);
+    }
+
+    #[test]
+    fn test_merge_concatenates_overrides() {
+        let mut base = json!({ "overrides": [{ "files": ["a.md"] }] });
+        merge(&mut base, json!({ "overrides": [{ "files": ["b.md"] }] }));
+        assert_eq!(base["overrides"], json!([{ "files": ["a.md"] }, { "files": ["b.md"] }]));
+    }
+
+    #[test]
+    fn test_merge_null_removes_key() {
+        let mut base = json!({ "rules": { "frontmatter-limit": { "models": {} }, "unique-name": true } });
+        merge(&mut base, json!({ "rules": { "frontmatter-limit": null } }));
+        assert_eq!(base["rules"], json!({ "unique-name": true }));
+    }
+
+    #[test]
+    fn test_take_extends_accepts_string_or_list() {
+        let mut single = json!({ "extends": "skills-lint:recommended" });
+        assert_eq!(take_extends(&mut single, "x").unwrap(), vec!["skills-lint:recommended"]);
+        assert!(single.get(EXTENDS_KEY).is_none());
+
+        let mut list = json!({ "extends": ["a.json", "b.json"] });
+        assert_eq!(take_extends(&mut list, "x").unwrap(), vec!["a.json", "b.json"]);
+
+        let mut invalid = json!({ "extends": 3 });
+        assert!(take_extends(&mut invalid, "x").is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use serde_json::{json, Value};
+
+use crate::config::extends::merge;
+use crate::config::supported_model_names;
+use crate::config::validate::{
+    suggest, MODEL_BUDGET_KEYS, RULE_NAMES, SIZE_BUDGET_KEYS, SIZE_LIMIT_KEYS, TOKEN_RULES,
+};
+use crate::errors::LintError;
+
+/// Config changes given on the command line, layered on top of the loaded config
+/// before it is validated.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFlags {
+    /// Assignments of the form `<rule>=<value>`, `<rule>.<model>[.<field>]=<value>` or,
+    /// for `size-limit`, `size-limit.<metric>[.<field>]=<value>`.
+    pub rules: Vec<String>,
+    /// Restrict token rules, overrides and pricing to these models.
+    pub models: Vec<String>,
+    /// Keep only these rules.
+    pub only_rules: Vec<String>,
+    /// Remove these rules.
+    pub disable_rules: Vec<String>,
+}
+
+impl ConfigFlags {
+    /// Apply the flags to a raw config value.
+    ///
+    /// Assignments are merged like an `extends` overlay, so `null` removes a key. Rule
+    /// selection is applied afterwards, so `--disable-rule` wins over `--rule`.
+    pub fn apply(&self, value: &mut Value) -> Result<(), LintError> {
+        for assignment in &self.rules {
+            merge(value, parse_assignment(assignment)?);
+        }
+
+        for rule in &self.only_rules {
+            check_name(&format!("--only-rule '{rule}'"), "rule", rule, RULE_NAMES)?;
+        }
+        for rule in &self.disable_rules {
+            check_name(&format!("--disable-rule '{rule}'"), "rule", rule, RULE_NAMES)?;
+        }
+        if let Some(rules) = value.get_mut("rules").and_then(Value::as_object_mut) {
+            if !self.only_rules.is_empty() {
+                rules.retain(|rule, _| self.only_rules.contains(rule));
+            }
+            for rule in &self.disable_rules {
+                rules.remove(rule);
+            }
+            // Overrides only adjust budgets of configured rules, so drop the rest.
+            let enabled: Vec<String> = rules.keys().cloned().collect();
+            if let Some(overrides) = value.get_mut("overrides").and_then(Value::as_array_mut) {
+                for entry in overrides {
+                    if let Some(rules) = entry.get_mut("rules").and_then(Value::as_object_mut) {
+                        rules.retain(|rule, _| enabled.contains(rule));
+                    }
+                }
+            }
+        }
+
+        if !self.models.is_empty() {
+            let supported = supported_model_names();
+            for model in &self.models {
+                check_name(&format!("--model '{model}'"), "model", model, &supported)?;
+            }
+            self.restrict_models(value);
+        }
+        Ok(())
+    }
+
+    /// Drop every model not listed in `--model` from token rules, overrides and pricing.
+    fn restrict_models(&self, value: &mut Value) {
+        let keep = |models: Option<&mut Value>| {
+            if let Some(models) = models.and_then(Value::as_object_mut) {
+                models.retain(|model, _| self.models.contains(model));
+            }
+        };
+        for &rule in TOKEN_RULES {
+            keep(value.pointer_mut(&format!("/rules/{rule}/models")));
+        }
+        keep(value.get_mut("pricing"));
+        if let Some(overrides) = value.get_mut("overrides").and_then(Value::as_array_mut) {
+            for entry in overrides {
+                keep(entry.pointer_mut("/rules/token-limit/models"));
+            }
+        }
+    }
+}
+
+/// Turn a `--rule` assignment into a config overlay.
+///
+/// Model names may contain dots (`gpt-3.5-turbo`), so the rule is everything before the
+/// first dot and the field is only split off when the key ends in a known budget field.
+fn parse_assignment(assignment: &str) -> Result<Value, LintError> {
+    let flag = format!("--rule '{assignment}'");
+    let Some((key, raw)) = assignment.split_once('=') else {
+        return Err(LintError::ConfigFlag(
+            flag,
+            "expected <rule>=<value> or <rule>.<model>.<field>=<value>".to_string(),
+        ));
+    };
+    // Anything that is not valid JSON, such as an encoding name, is taken as a string.
+    let value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+
+    let (rule, rest) = match key.split_once('.') {
+        Some((rule, rest)) => (rule, Some(rest)),
+        None => (key, None),
+    };
+    check_name(&flag, "rule", rule, RULE_NAMES)?;
+
+    let Some(rest) = rest else {
+        return Ok(json!({ "rules": { rule: value } }));
+    };
+    if rule == "size-limit" {
+        let (metric, field) = match rest.split_once('.') {
+            Some((metric, field)) => (metric, Some(field)),
+            None => (rest, None),
+        };
+        check_name(&flag, "metric", metric, SIZE_LIMIT_KEYS)?;
+        let budget = match field {
+            Some(field) => {
+                check_name(&flag, "budget field", field, SIZE_BUDGET_KEYS)?;
+                json!({ field: value })
+            }
+            None => value,
+        };
+        return Ok(json!({ "rules": { rule: { metric: budget } } }));
+    }
+    if !TOKEN_RULES.contains(&rule) {
+        return Err(LintError::ConfigFlag(flag, format!("\"{rule}\" has no per-model budgets")));
+    }
+
+    let (model, field) = MODEL_BUDGET_KEYS
+        .iter()
+        .find_map(|field| rest.strip_suffix(&format!(".{field}")).map(|model| (model, Some(*field))))
+        .unwrap_or((rest, None));
+    check_name(&flag, "model", model, &supported_model_names())?;
+
+    let budget = match field {
+        Some(field) => json!({ field: value }),
+        None => value,
+    };
+    Ok(json!({ "rules": { rule: { "models": { model: budget } } } }))
+}
+
+fn check_name(flag: &str, kind: &str, name: &str, allowed: &[&str]) -> Result<(), LintError> {
+    if allowed.contains(&name) {
+        return Ok(());
+    }
+    let mut message = format!("unknown {kind} \"{name}\"");
+    match suggest(name, allowed) {
+        Some(suggestion) => message.push_str(&format!(" (did you mean \"{suggestion}\"?)")),
+        None => message.push_str(&format!(". Expected one of: {}", allowed.join(", "))),
+    }
+    Err(LintError::ConfigFlag(flag.to_string(), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> Value {
+        json!({
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": { "models": {
+                    "gpt-4o": { "warning": 8000, "error": 16000 },
+                    "gpt-3.5-turbo": { "warning": 4000, "error": 8000 }
+                } },
+                "skill-structure": true,
+                "unique-name": true
+            },
+            "overrides": [
+                { "files": ["big.md"], "rules": { "token-limit": { "models": { "gpt-4o": { "error": 32000 } } } } }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_rule_assignments() {
+        let mut value = base();
+        let flags = ConfigFlags {
+            rules: vec![
+                "token-limit.gpt-4o.error=12000".to_string(),
+                "token-limit.gpt-3.5-turbo.encoding=o200k_base".to_string(),
+                "skill-structure=false".to_string(),
+                "size-limit.maxLines.error=500".to_string(),
+            ],
+            ..Default::default()
+        };
+        flags.apply(&mut value).unwrap();
+        assert_eq!(value["rules"]["token-limit"]["models"]["gpt-4o"], json!({ "warning": 8000, "error": 12000 }));
+        assert_eq!(value["rules"]["token-limit"]["models"]["gpt-3.5-turbo"]["encoding"], "o200k_base");
+        assert_eq!(value["rules"]["skill-structure"], false);
+        assert_eq!(value["rules"]["size-limit"]["maxLines"], json!({ "error": 500 }));
+    }
+
+    #[test]
+    fn test_rule_assignment_errors() {
+        let err = parse_assignment("token-limt.gpt-4o.error=1").unwrap_err().to_string();
+        assert!(err.contains("did you mean \"token-limit\""), "{err}");
+        let err = parse_assignment("token-limit.gpt4o.error=1").unwrap_err().to_string();
+        assert!(err.contains("did you mean \"gpt-4o\""), "{err}");
+        assert!(parse_assignment("token-limit.gpt-4o.error").is_err());
+        assert!(parse_assignment("unique-name.gpt-4o=true").is_err());
+        let err = parse_assignment("size-limit.maxLine.error=1").unwrap_err().to_string();
+        assert!(err.contains("did you mean \"maxLines\""), "{err}");
+    }
+
+    #[test]
+    fn test_only_and_disable_rules() {
+        let mut value = base();
+        let flags = ConfigFlags {
+            only_rules: vec!["token-limit".to_string(), "unique-name".to_string()],
+            disable_rules: vec!["unique-name".to_string()],
+            ..Default::default()
+        };
+        flags.apply(&mut value).unwrap();
+        let rules: Vec<&String> = value["rules"].as_object().unwrap().keys().collect();
+        assert_eq!(rules, vec!["token-limit"]);
+        assert!(value.get("overrides").is_some());
+
+        let mut value = base();
+        let flags = ConfigFlags {
+            disable_rules: vec!["token-limit".to_string()],
+            ..Default::default()
+        };
+        flags.apply(&mut value).unwrap();
+        assert_eq!(value["overrides"][0]["rules"], json!({}));
+    }
+
+    #[test]
+    fn test_restrict_models_includes_overrides() {
+        let mut value = base();
+        let flags = ConfigFlags {
+            models: vec!["gpt-3.5-turbo".to_string()],
+            ..Default::default()
+        };
+        flags.apply(&mut value).unwrap();
+        let models: Vec<&String> = value["rules"]["token-limit"]["models"].as_object().unwrap().keys().collect();
+        assert_eq!(models, vec!["gpt-3.5-turbo"]);
+        assert_eq!(value["overrides"][0]["rules"]["token-limit"]["models"], json!({}));
+
+        let unknown = ConfigFlags {
+            models: vec!["gpt-6".to_string()],
+            ..Default::default()
+        };
+        assert!(unknown.apply(&mut base()).is_err());
+    }
+}
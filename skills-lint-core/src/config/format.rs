@@ -0,0 +1,109 @@
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::config::{is_package_json, jsonc, PACKAGE_JSON_KEY};
+use crate::errors::LintError;
+
+/// File formats accepted for config files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// JSON, with comments and trailing commas allowed.
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Pick the format from the file extension. Unknown extensions are read as JSON.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml" | "yml") => ConfigFormat::Yaml,
+            Some("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+}
+
+/// Parse config content in the given format into raw JSON.
+pub fn parse_str(content: &str, format: ConfigFormat) -> Result<Value, String> {
+    match format {
+        ConfigFormat::Json => serde_json::from_str(&jsonc::strip(content)).map_err(|e| e.to_string()),
+        ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+        ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+    }
+}
+
+/// Parse config content read from `path` into raw JSON, unwrapping the
+/// `"skills-lint"` key of a `package.json`.
+pub fn parse_value(path: &Path, content: &str) -> Result<Value, LintError> {
+    let display = path.display().to_string();
+    let mut value = parse_str(content, ConfigFormat::from_path(path)).map_err(|e| LintError::ConfigParse(display.clone(), e))?;
+
+    if !is_package_json(path) {
+        return Ok(value);
+    }
+    value
+        .get_mut(PACKAGE_JSON_KEY)
+        .map(Value::take)
+        .ok_or(LintError::PackageJsonKey(display, PACKAGE_JSON_KEY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(ConfigFormat::from_path(Path::new(".skills-lint.config.json")), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path(Path::new(".skills-lint.config.yaml")), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path(Path::new(".skills-lint.config.yml")), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path(Path::new(".skills-lint.config.toml")), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path(Path::new("package.json")), ConfigFormat::Json);
+    }
+
+    #[test]
+    fn test_yaml_and_toml_parse_to_same_value() {
+        let yaml = r#"
+# Budgets for the agent stack
+patterns:
+  - "./.github/**/SKILL.md"
+rules:
+  token-limit:
+    models:
+      gpt-4o: { warning: 8000, error: 16000 }
+  skill-structure: true
+"#;
+        let toml = r#"
+# Budgets for the agent stack
+patterns = ["./.github/**/SKILL.md"]
+
+[rules]
+skill-structure = true
+
+[rules.token-limit.models.gpt-4o]
+warning = 8000
+error = 16000
+"#;
+        let json = r#"{
+            // Budgets for the agent stack
+            "patterns": ["./.github/**/SKILL.md"],
+            "rules": {
+                "token-limit": { "models": { "gpt-4o": { "warning": 8000, "error": 16000 } } },
+                "skill-structure": true,
+            },
+        }"#;
+        let from_yaml = parse_value(Path::new("c.yaml"), yaml).unwrap();
+        let from_toml = parse_value(Path::new("c.toml"), toml).unwrap();
+        let from_json = parse_value(Path::new("c.json"), json).unwrap();
+        assert_eq!(from_yaml, from_json);
+        assert_eq!(from_toml, from_json);
+    }
+
+    #[test]
+    fn test_json_error_keeps_line_number() {
+        let json = "{\n  // comment\n  \"patterns\": [1 2]\n}";
+        let err = parse_value(Path::new("c.json"), json).unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{err}");
+    }
+}
@@ -0,0 +1,117 @@
+/// Convert JSONC (JSON with comments and trailing commas) into plain JSON.
+///
+/// Comments and trailing commas are replaced with spaces rather than removed, so
+/// line and column numbers in parse errors still point into the original file.
+pub fn strip(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '"' => {
+                // Copy the string literal verbatim, honouring escapes.
+                out.push('"');
+                i += 1;
+                while i < chars.len() {
+                    let c = chars[i];
+                    out.push(c);
+                    i += 1;
+                    if c == '\\' && i < chars.len() {
+                        out.push(chars[i]);
+                        i += 1;
+                    } else if c == '"' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    out.push(' ');
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                out.push_str("  ");
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    out.push(if chars[i] == '\n' { '\n' } else { ' ' });
+                    i += 1;
+                }
+                if i < chars.len() {
+                    out.push_str("  ");
+                    i += 2;
+                }
+            }
+            ',' if is_trailing_comma(&chars, i + 1) => {
+                out.push(' ');
+                i += 1;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// Returns true if the next significant character after `from` closes an object or array.
+fn is_trailing_comma(chars: &[char], from: usize) -> bool {
+    let mut i = from;
+    while i < chars.len() {
+        match chars[i] {
+            c if c.is_whitespace() => i += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            '}' | ']' => return true,
+            _ => return false,
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_comments_and_trailing_commas() {
+        let jsonc = r#"{
+            // line comment
+            "patterns": ["*.md",], /* block
+            comment */
+            "rules": { "skill-structure": true, },
+        }"#;
+        let value: serde_json::Value = serde_json::from_str(&strip(jsonc)).unwrap();
+        assert_eq!(value["patterns"][0], "*.md");
+        assert_eq!(value["rules"]["skill-structure"], true);
+    }
+
+    #[test]
+    fn test_strip_preserves_strings() {
+        let jsonc = r#"{ "a": "http://x // not a comment", "b": "quote \" /* kept */", }"#;
+        let value: serde_json::Value = serde_json::from_str(&strip(jsonc)).unwrap();
+        assert_eq!(value["a"], "http://x // not a comment");
+        assert_eq!(value["b"], "quote \" /* kept */");
+    }
+
+    #[test]
+    fn test_strip_preserves_positions() {
+        let jsonc = "{\n  // comment\n  \"a\": 1,\n}";
+        let stripped = strip(jsonc);
+        assert_eq!(stripped.lines().count(), jsonc.lines().count());
+        assert_eq!(stripped.len(), jsonc.len());
+    }
+}
@@ -0,0 +1,1189 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub mod budget;
+pub mod effective;
+pub mod env;
+pub mod extends;
+pub mod flags;
+pub mod format;
+pub mod jsonc;
+pub mod presets;
+pub mod schema;
+pub mod validate;
+
+use crate::cache::{user_cache_dir, CACHE_DIR, USER_CACHE_LOCATION};
+use crate::discovery;
+use crate::errors::LintError;
+use budget::Budget;
+use flags::ConfigFlags;
+use format::ConfigFormat;
+
+/// Supported models, their default encodings and max input size in tokens
+/// (context window minus max output).
+const SUPPORTED_MODELS: &[(&str, &str, usize)] = &[
+    ("gpt-5", "o200k_base", 272_000),
+    ("gpt-4o", "o200k_base", 111_616),
+    ("gpt-4o-mini", "o200k_base", 111_616),
+    ("gpt-4-turbo", "cl100k_base", 123_904),
+    ("gpt-4", "cl100k_base", 4_096),
+    ("gpt-3.5-turbo", "cl100k_base", 12_289),
+];
+
+/// Config file names searched for in each directory, in order of precedence.
+pub const CONFIG_FILE_NAMES: &[&str] = &[
+    ".skills-lint.config.json",
+    ".skills-lint.config.yaml",
+    ".skills-lint.config.yml",
+    ".skills-lint.config.toml",
+    "skills-lint.config.json",
+];
+
+/// `package.json` key that may hold the config instead of a dedicated file.
+pub const PACKAGE_JSON_KEY: &str = "skills-lint";
+
+const PACKAGE_JSON: &str = "package.json";
+
+/// Return the default encoding for a supported model, or None if unsupported.
+pub fn default_encoding(model: &str) -> Option<&'static str> {
+    SUPPORTED_MODELS
+        .iter()
+        .find(|(name, _, _)| *name == model)
+        .map(|(_, enc, _)| *enc)
+}
+
+/// Return the max input size in tokens for a supported model, or None if unsupported.
+///
+/// Percentage budgets are resolved against this size.
+pub fn max_input(model: &str) -> Option<usize> {
+    SUPPORTED_MODELS
+        .iter()
+        .find(|(name, _, _)| *name == model)
+        .map(|(_, _, max_input)| *max_input)
+}
+
+/// Return a list of all supported model names.
+pub fn supported_model_names() -> Vec<&'static str> {
+    SUPPORTED_MODELS.iter().map(|(name, _, _)| *name).collect()
+}
+
+/// Top-level config loaded from `.skills-lint.config.json`.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// JSON Schema reference for editor support. Not used by the linter.
+    #[serde(rename = "$schema", default)]
+    pub schema: Option<String>,
+    /// Glob patterns of skill files to lint, relative to the config file.
+    pub patterns: Vec<String>,
+    /// Gitignore-style patterns of paths to exclude from discovery.
+    #[serde(rename = "ignorePatterns", default)]
+    pub ignore_patterns: Vec<String>,
+    /// Skip hidden paths and paths excluded by `.gitignore` or `.ignore` files.
+    #[serde(rename = "respectIgnoreFiles", default = "default_respect_ignore_files")]
+    pub respect_ignore_files: bool,
+    pub rules: RulesConfig,
+    /// Per-file budget overrides, applied in order.
+    #[serde(default)]
+    pub overrides: Vec<OverrideEntry>,
+    /// Cache token counts between runs.
+    #[serde(default = "default_cache")]
+    pub cache: bool,
+    /// Cache directory, relative to the config file, or `"user"` for the shared per-user
+    /// cache under `XDG_CACHE_HOME`. Defaults to `.skills-lint-cache` next to the config.
+    #[serde(rename = "cacheLocation", default)]
+    pub cache_location: Option<String>,
+    /// Input token prices per model, used to estimate costs with `--cost`.
+    #[serde(default)]
+    #[schemars(schema_with = "schema::model_map::<ModelPrice>")]
+    pub pricing: HashMap<String, ModelPrice>,
+    /// Directory containing the config file. Patterns, override `files` and the
+    /// cache directory are resolved against it, and reported paths are relative to it.
+    #[serde(skip, default = "default_root")]
+    pub root: PathBuf,
+}
+
+fn default_cache() -> bool {
+    true
+}
+
+fn default_respect_ignore_files() -> bool {
+    true
+}
+
+fn default_root() -> PathBuf {
+    PathBuf::from(".")
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RulesConfig {
+    #[serde(rename = "token-limit", default)]
+    pub token_limit: Option<TokenLimitConfig>,
+    #[serde(rename = "skill-index-budget", default)]
+    pub skill_index_budget: Option<SkillIndexBudgetConfig>,
+    #[serde(rename = "frontmatter-limit", default)]
+    pub frontmatter_limit: Option<FrontmatterLimitConfig>,
+    #[serde(rename = "skill-structure", default)]
+    pub skill_structure: Option<bool>,
+    #[serde(rename = "unique-name", default)]
+    pub unique_name: Option<bool>,
+    #[serde(rename = "unique-description", default)]
+    pub unique_description: Option<bool>,
+    #[serde(rename = "size-limit", default)]
+    pub size_limit: Option<SizeLimitConfig>,
+}
+
+impl RulesConfig {
+    /// Returns true if at least one rule is configured and enabled.
+    ///
+    /// Token rules only count when they budget at least one model.
+    pub fn any_enabled(&self) -> bool {
+        self.token_limit.as_ref().is_some_and(|rule| !rule.models.is_empty())
+            || self.skill_index_budget.as_ref().is_some_and(|rule| !rule.models.is_empty())
+            || self.frontmatter_limit.as_ref().is_some_and(|rule| !rule.models.is_empty())
+            || self.skill_structure == Some(true)
+            || self.unique_name == Some(true)
+            || self.unique_description == Some(true)
+            || self.size_limit.as_ref().is_some_and(SizeLimitConfig::has_metrics)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TokenLimitConfig {
+    #[schemars(schema_with = "schema::model_map::<ModelBudget>")]
+    pub models: HashMap<String, ModelBudget>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SkillIndexBudgetConfig {
+    #[schemars(schema_with = "schema::model_map::<ModelBudget>")]
+    pub models: HashMap<String, ModelBudget>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FrontmatterLimitConfig {
+    #[schemars(schema_with = "schema::model_map::<ModelBudget>")]
+    pub models: HashMap<String, ModelBudget>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ModelBudget {
+    #[serde(default)]
+    #[schemars(schema_with = "schema::encoding")]
+    pub encoding: Option<String>,
+    /// Token count at which a warning is reported.
+    pub warning: Budget,
+    /// Token count at which an error is reported.
+    pub error: Budget,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ModelPrice {
+    /// Price in USD per 1M input tokens.
+    pub input: f64,
+}
+
+/// A size measure checked by the `size-limit` rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeMetric {
+    Bytes,
+    Lines,
+    Words,
+}
+
+impl SizeMetric {
+    pub const ALL: [SizeMetric; 3] = [SizeMetric::Bytes, SizeMetric::Lines, SizeMetric::Words];
+
+    /// Config key of the metric, e.g. `maxBytes`.
+    pub fn key(self) -> &'static str {
+        match self {
+            SizeMetric::Bytes => "maxBytes",
+            SizeMetric::Lines => "maxLines",
+            SizeMetric::Words => "maxWords",
+        }
+    }
+
+    /// Label shown in findings, e.g. `bytes`.
+    pub fn label(self) -> &'static str {
+        match self {
+            SizeMetric::Bytes => "bytes",
+            SizeMetric::Lines => "lines",
+            SizeMetric::Words => "words",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SizeLimitConfig {
+    /// File size in bytes.
+    #[serde(rename = "maxBytes", default)]
+    pub max_bytes: Option<SizeBudget>,
+    /// Number of lines.
+    #[serde(rename = "maxLines", default)]
+    pub max_lines: Option<SizeBudget>,
+    /// Number of whitespace-separated words.
+    #[serde(rename = "maxWords", default)]
+    pub max_words: Option<SizeBudget>,
+}
+
+impl SizeLimitConfig {
+    /// Returns true if at least one metric is configured.
+    pub fn has_metrics(&self) -> bool {
+        SizeMetric::ALL.into_iter().any(|metric| self.get(metric).is_some())
+    }
+
+    pub fn get(&self, metric: SizeMetric) -> Option<&SizeBudget> {
+        match metric {
+            SizeMetric::Bytes => self.max_bytes.as_ref(),
+            SizeMetric::Lines => self.max_lines.as_ref(),
+            SizeMetric::Words => self.max_words.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SizeBudget {
+    /// Size at which a warning is reported.
+    pub warning: usize,
+    /// Size at which an error is reported.
+    pub error: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OverrideEntry {
+    /// Glob patterns of the files this entry applies to, relative to the config file.
+    pub files: Vec<String>,
+    pub rules: OverrideRules,
+}
+
+impl OverrideEntry {
+    /// Returns true if any of the entry's `files` globs matches the root-relative `file`.
+    pub fn matches(&self, file: &str) -> bool {
+        let file = discovery::normalize(file);
+        self.files.iter().any(|f| {
+            let f = discovery::normalize(f);
+            f == file
+                || glob::Pattern::new(f).is_ok_and(|p| p.matches_with(file, discovery::MATCH_OPTIONS))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OverrideRules {
+    #[serde(rename = "token-limit", default)]
+    pub token_limit: Option<OverrideTokenLimit>,
+    #[serde(rename = "size-limit", default)]
+    pub size_limit: Option<OverrideSizeLimit>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OverrideTokenLimit {
+    #[schemars(schema_with = "schema::model_map::<OverrideModelBudget>")]
+    pub models: HashMap<String, OverrideModelBudget>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OverrideModelBudget {
+    #[serde(default)]
+    #[schemars(schema_with = "schema::encoding")]
+    pub encoding: Option<String>,
+    pub warning: Option<Budget>,
+    pub error: Option<Budget>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OverrideSizeLimit {
+    #[serde(rename = "maxBytes", default)]
+    pub max_bytes: Option<OverrideSizeBudget>,
+    #[serde(rename = "maxLines", default)]
+    pub max_lines: Option<OverrideSizeBudget>,
+    #[serde(rename = "maxWords", default)]
+    pub max_words: Option<OverrideSizeBudget>,
+}
+
+impl OverrideSizeLimit {
+    pub fn get(&self, metric: SizeMetric) -> Option<&OverrideSizeBudget> {
+        match metric {
+            SizeMetric::Bytes => self.max_bytes.as_ref(),
+            SizeMetric::Lines => self.max_lines.as_ref(),
+            SizeMetric::Words => self.max_words.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OverrideSizeBudget {
+    pub warning: Option<usize>,
+    pub error: Option<usize>,
+}
+
+/// Search `start` and its parent directories for a config file.
+///
+/// Each directory is checked for the names in [`CONFIG_FILE_NAMES`], then for a
+/// `package.json` with a `"skills-lint"` key. The search stops after the first
+/// directory containing `.git`, or at the filesystem root.
+pub fn locate(start: &Path) -> Result<PathBuf, LintError> {
+    for dir in start.ancestors() {
+        for name in CONFIG_FILE_NAMES {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                return Ok(candidate);
+            }
+        }
+
+        let package_json = dir.join(PACKAGE_JSON);
+        if package_json.is_file() && has_package_json_key(&package_json) {
+            return Ok(package_json);
+        }
+
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    Err(LintError::ConfigNotFound(start.display().to_string()))
+}
+
+/// Canonicalize `path`, or its closest existing ancestor followed by the rest of it when
+/// the path does not exist.
+fn canonical_path(path: &Path) -> std::io::Result<PathBuf> {
+    match path.canonicalize() {
+        Ok(path) => Ok(path),
+        Err(e) => match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) => {
+                let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+                Ok(canonical_path(parent)?.join(name))
+            }
+            _ => Err(e),
+        },
+    }
+}
+
+fn has_package_json_key(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&jsonc::strip(&content)).ok())
+        .is_some_and(|value| value.get(PACKAGE_JSON_KEY).is_some())
+}
+
+fn is_package_json(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == PACKAGE_JSON)
+}
+
+/// Name used in errors for configs that are not read from a file.
+pub const INLINE_CONFIG: &str = "<inline config>";
+
+impl Config {
+    /// Load config from a JSON (or JSONC), YAML or TOML file, or from the `"skills-lint"` key of a `package.json`.
+    ///
+    /// Any `extends` entries are resolved and merged before the config is validated.
+    pub fn load(path: &Path) -> Result<Self, LintError> {
+        Self::load_with(path, &ConfigFlags::default())
+    }
+
+    /// Load config like [`Config::load`], applying command-line `flags` on top of the
+    /// merged config before it is validated.
+    pub fn load_with(path: &Path, flags: &ConfigFlags) -> Result<Self, LintError> {
+        let loaded = extends::load_value(path)?;
+        let mut value = loaded.value.clone();
+        flags.apply(&mut value)?;
+        let file = path.display().to_string();
+        let mut config: Config = serde_path_to_error::deserialize(value.clone()).map_err(|e| match validate::type_error(&e) {
+            Some(issue) => {
+                let mut issues = vec![issue];
+                validate::attach_own_source(&mut issues, &file, &loaded.content, &loaded.own, &value);
+                LintError::ConfigInvalid(issues)
+            }
+            None => LintError::ConfigParse(file.clone(), e.into_inner().to_string()),
+        })?;
+        config.root = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => default_root(),
+        };
+        config.validate().map_err(|e| match e {
+            LintError::ConfigInvalid(mut issues) => {
+                validate::attach_own_source(&mut issues, &file, &loaded.content, &loaded.own, &value);
+                LintError::ConfigInvalid(issues)
+            }
+            e => e,
+        })?;
+        Ok(config)
+    }
+
+    /// Build a config from a value held in memory, e.g. one stored in a database.
+    ///
+    /// Unlike [`Config::load`], nothing is read from the filesystem or the environment:
+    /// `extends` may only name presets and `${VAR}` references are kept as written. The
+    /// root is `.`.
+    pub fn from_value(value: serde_json::Value) -> Result<Self, LintError> {
+        let value = extends::resolve_inline(value, INLINE_CONFIG)?;
+        let config: Config = serde_json::from_value(value)
+            .map_err(|e| LintError::ConfigParse(INLINE_CONFIG.to_string(), e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse config text in the given format, like [`Config::from_value`].
+    pub fn parse(content: &str, format: ConfigFormat) -> Result<Self, LintError> {
+        let value = format::parse_str(content, format)
+            .map_err(|e| LintError::ConfigParse(INLINE_CONFIG.to_string(), e))?;
+        Self::from_value(value)
+    }
+
+    /// Resolve a root-relative file path to a path on disk.
+    pub fn resolve_path(&self, file: &str) -> PathBuf {
+        self.root.join(file)
+    }
+
+    /// Express a path given relative to the current directory relative to the config root.
+    ///
+    /// A path that lies outside the root is returned as an absolute path, which
+    /// [`Config::resolve_path`] leaves as it is.
+    pub fn relativize(&self, path: &Path) -> String {
+        let Ok(file) = canonical_path(path) else {
+            return path.display().to_string();
+        };
+        match canonical_path(&self.root) {
+            Ok(root) => match file.strip_prefix(root) {
+                Ok(relative) => relative.display().to_string(),
+                Err(_) => file.display().to_string(),
+            },
+            Err(_) => file.display().to_string(),
+        }
+    }
+
+    /// Directory holding the token cache.
+    ///
+    /// Falls back to the default directory under the config root when the user cache is
+    /// selected but no home or cache directory is known.
+    pub fn cache_dir(&self) -> PathBuf {
+        match self.cache_location.as_deref() {
+            None => self.root.join(CACHE_DIR),
+            Some(USER_CACHE_LOCATION) => user_cache_dir().unwrap_or_else(|| self.root.join(CACHE_DIR)),
+            Some(location) => self.root.join(location),
+        }
+    }
+
+    /// Validate that at least one rule is enabled, all model names in the config are
+    /// supported, and budgets, encodings and overrides are consistent.
+    fn validate(&self) -> Result<(), LintError> {
+        if !self.rules.any_enabled() {
+            return Err(LintError::NoRulesEnabled);
+        }
+        if let Some(ref tl) = self.rules.token_limit {
+            for model in tl.models.keys() {
+                if default_encoding(model).is_none() {
+                    return Err(LintError::UnsupportedModel(
+                        model.clone(),
+                        supported_model_names(),
+                    ));
+                }
+            }
+        }
+        if let Some(ref sib) = self.rules.skill_index_budget {
+            for model in sib.models.keys() {
+                if default_encoding(model).is_none() {
+                    return Err(LintError::UnsupportedModel(
+                        model.clone(),
+                        supported_model_names(),
+                    ));
+                }
+            }
+        }
+        if let Some(ref fl) = self.rules.frontmatter_limit {
+            for model in fl.models.keys() {
+                if default_encoding(model).is_none() {
+                    return Err(LintError::UnsupportedModel(
+                        model.clone(),
+                        supported_model_names(),
+                    ));
+                }
+            }
+        }
+        for model in self.pricing.keys() {
+            if default_encoding(model).is_none() {
+                return Err(LintError::UnsupportedModel(
+                    model.clone(),
+                    supported_model_names(),
+                ));
+            }
+        }
+        for entry in &self.overrides {
+            let Some(ref tl) = entry.rules.token_limit else {
+                continue;
+            };
+            for model in tl.models.keys() {
+                if default_encoding(model).is_none() {
+                    return Err(LintError::UnsupportedModel(
+                        model.clone(),
+                        supported_model_names(),
+                    ));
+                }
+            }
+        }
+        let issues = validate::semantic(self);
+        if !issues.is_empty() {
+            return Err(LintError::ConfigInvalid(issues));
+        }
+        Ok(())
+    }
+
+    /// Resolve the effective token-limit budget for a given file and model.
+    /// Applies overrides on top of the global config.
+    pub fn resolve_token_limit(&self, file: &str, model: &str) -> Option<ResolvedBudget> {
+        let tl = self.rules.token_limit.as_ref()?;
+        let global = tl.models.get(model)?;
+
+        let mut encoding = global.encoding.clone();
+        let mut warning = global.warning;
+        let mut error = global.error;
+        let mut override_index = None;
+
+        for (index, entry) in self.overrides.iter().enumerate() {
+            if entry.matches(file) {
+                if let Some(ovr) = entry.rules.token_limit.as_ref().and_then(|tl| tl.models.get(model)) {
+                    if let Some(ref enc) = ovr.encoding {
+                        encoding = Some(enc.clone());
+                    }
+                    if let Some(w) = ovr.warning {
+                        warning = w;
+                    }
+                    if let Some(e) = ovr.error {
+                        error = e;
+                    }
+                    override_index = Some(index);
+                }
+            }
+        }
+
+        let default_enc = default_encoding(model)
+            .unwrap_or("cl100k_base")
+            .to_string();
+
+        let max = max_input(model).unwrap_or_default();
+        Some(ResolvedBudget {
+            encoding: encoding.unwrap_or(default_enc),
+            warning: warning.resolve(max),
+            error: error.resolve(max),
+            override_index,
+        })
+    }
+
+    /// Resolve the frontmatter-limit budget for a given model (no per-file overrides).
+    pub fn resolve_frontmatter_limit(&self, model: &str) -> Option<ResolvedBudget> {
+        let fl = self.rules.frontmatter_limit.as_ref()?;
+        let budget = fl.models.get(model)?;
+
+        let default_enc = default_encoding(model)
+            .unwrap_or("cl100k_base")
+            .to_string();
+
+        let max = max_input(model).unwrap_or_default();
+        Some(ResolvedBudget {
+            encoding: budget.encoding.clone().unwrap_or(default_enc),
+            warning: budget.warning.resolve(max),
+            error: budget.error.resolve(max),
+            override_index: None,
+        })
+    }
+
+    /// Resolve the skill-index-budget for a given model (no per-file overrides).
+    pub fn resolve_skill_index_budget(&self, model: &str) -> Option<ResolvedBudget> {
+        let sib = self.rules.skill_index_budget.as_ref()?;
+        let budget = sib.models.get(model)?;
+
+        let default_enc = default_encoding(model)
+            .unwrap_or("cl100k_base")
+            .to_string();
+
+        let max = max_input(model).unwrap_or_default();
+        Some(ResolvedBudget {
+            encoding: budget.encoding.clone().unwrap_or(default_enc),
+            warning: budget.warning.resolve(max),
+            error: budget.error.resolve(max),
+            override_index: None,
+        })
+    }
+
+    /// Resolve the size-limit budget for a given file and metric.
+    /// Applies overrides on top of the global config.
+    pub fn resolve_size_limit(&self, file: &str, metric: SizeMetric) -> Option<ResolvedSizeBudget> {
+        let global = self.rules.size_limit.as_ref()?.get(metric)?;
+
+        let mut warning = global.warning;
+        let mut error = global.error;
+        let mut override_index = None;
+
+        for (index, entry) in self.overrides.iter().enumerate() {
+            if entry.matches(file) {
+                if let Some(ovr) = entry.rules.size_limit.as_ref().and_then(|sl| sl.get(metric)) {
+                    if let Some(w) = ovr.warning {
+                        warning = w;
+                    }
+                    if let Some(e) = ovr.error {
+                        error = e;
+                    }
+                    override_index = Some(index);
+                }
+            }
+        }
+
+        Some(ResolvedSizeBudget {
+            warning,
+            error,
+            override_index,
+        })
+    }
+}
+
+/// Fully resolved size-limit budget for a file × metric pair.
+#[derive(Debug, Clone)]
+pub struct ResolvedSizeBudget {
+    pub warning: usize,
+    pub error: usize,
+    /// Index into `overrides` of the last entry that changed this budget, if any.
+    pub override_index: Option<usize>,
+}
+
+/// Fully resolved budget for a file × model pair, with percentages converted to token counts.
+#[derive(Debug, Clone)]
+pub struct ResolvedBudget {
+    pub encoding: String,
+    pub warning: usize,
+    pub error: usize,
+    /// Index into `overrides` of the last entry that changed this budget, if any.
+    pub override_index: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_global_budget() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4": { "warning": 8000, "error": 12000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let budget = config.resolve_token_limit("foo.md", "gpt-4").unwrap();
+        assert_eq!(budget.warning, 8000);
+        assert_eq!(budget.error, 12000);
+        assert_eq!(budget.encoding, "cl100k_base");
+    }
+
+    #[test]
+    fn test_resolve_with_override() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4o": { "warning": 8000, "error": 12000 }
+                    }
+                }
+            },
+            "overrides": [{
+                "files": ["big.md"],
+                "rules": {
+                    "token-limit": {
+                        "models": {
+                            "gpt-4o": { "warning": 16000, "error": 24000 }
+                        }
+                    }
+                }
+            }]
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+
+        let normal = config.resolve_token_limit("foo.md", "gpt-4o").unwrap();
+        assert_eq!(normal.warning, 8000);
+        assert_eq!(normal.encoding, "o200k_base");
+        assert_eq!(normal.override_index, None);
+
+        let overridden = config.resolve_token_limit("big.md", "gpt-4o").unwrap();
+        assert_eq!(overridden.override_index, Some(0));
+        assert_eq!(overridden.warning, 16000);
+        assert_eq!(overridden.error, 24000);
+        assert_eq!(overridden.encoding, "o200k_base");
+    }
+
+    #[test]
+    fn test_resolve_percentage_budgets() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-5": { "warning": "5%", "error": "10%" },
+                        "gpt-4": { "warning": "50%", "error": 4000 }
+                    }
+                },
+                "frontmatter-limit": {
+                    "models": {
+                        "gpt-5": { "warning": "0.5%", "error": "1%" }
+                    }
+                }
+            },
+            "overrides": [{
+                "files": ["big.md"],
+                "rules": { "token-limit": { "models": { "gpt-5": { "error": "20%" } } } }
+            }]
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.validate().is_ok());
+
+        let gpt5 = config.resolve_token_limit("foo.md", "gpt-5").unwrap();
+        assert_eq!((gpt5.warning, gpt5.error), (13_600, 27_200));
+        let gpt4 = config.resolve_token_limit("foo.md", "gpt-4").unwrap();
+        assert_eq!((gpt4.warning, gpt4.error), (2_048, 4_000));
+        let big = config.resolve_token_limit("big.md", "gpt-5").unwrap();
+        assert_eq!(big.error, 54_400);
+        let frontmatter = config.resolve_frontmatter_limit("gpt-5").unwrap();
+        assert_eq!((frontmatter.warning, frontmatter.error), (1_360, 2_720));
+    }
+
+    #[test]
+    fn test_validate_compares_resolved_percentages() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4": { "warning": "50%", "error": 1000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(matches!(config.validate(), Err(LintError::ConfigInvalid(_))));
+    }
+
+    #[test]
+    fn test_override_files_are_root_relative_globs() {
+        let json = r#"{
+            "patterns": ["./skills/**/SKILL.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4o": { "warning": 8000, "error": 12000 }
+                    }
+                }
+            },
+            "overrides": [{
+                "files": ["./skills/vendor/**/SKILL.md"],
+                "rules": {
+                    "token-limit": {
+                        "models": {
+                            "gpt-4o": { "warning": 16000 }
+                        }
+                    }
+                }
+            }]
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+
+        let vendored = config.resolve_token_limit("skills/vendor/pack/SKILL.md", "gpt-4o").unwrap();
+        assert_eq!(vendored.warning, 16000);
+        assert_eq!(vendored.error, 12000);
+
+        let ours = config.resolve_token_limit("skills/ours/SKILL.md", "gpt-4o").unwrap();
+        assert_eq!(ours.warning, 8000);
+    }
+
+    #[test]
+    fn test_load_sets_root_to_config_directory() {
+        let mut config = Config::load(Path::new("../.skills-lint.config.json")).unwrap();
+        assert_eq!(config.root, PathBuf::from(".."));
+        assert_eq!(config.resolve_path(".github/x.md"), PathBuf::from("../.github/x.md"));
+        assert_eq!(config.cache_dir(), PathBuf::from("..").join(CACHE_DIR));
+
+        config.cache_location = Some("build/lint-cache".to_string());
+        assert_eq!(config.cache_dir(), PathBuf::from("../build/lint-cache"));
+        config.cache_location = Some("/var/cache/skills".to_string());
+        assert_eq!(config.cache_dir(), PathBuf::from("/var/cache/skills"));
+        assert_eq!(
+            config.relativize(Path::new("../.github/skills/foo/SKILL.md")),
+            ".github/skills/foo/SKILL.md"
+        );
+        assert_eq!(config.relativize(Path::new("../missing/SKILL.md")), "missing/SKILL.md");
+
+        config.root = PathBuf::from("src");
+        let outside = config.relativize(Path::new("Cargo.toml"));
+        assert!(Path::new(&outside).is_absolute());
+        assert_eq!(config.resolve_path(&outside), Path::new("Cargo.toml").canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_parse_package_json_key() {
+        let json = r#"{
+            "name": "my-skills",
+            "skills-lint": {
+                "patterns": ["*.md"],
+                "rules": { "skill-structure": true }
+            }
+        }"#;
+        let value = format::parse_value(Path::new("package.json"), json).unwrap();
+        let config: Config = serde_json::from_value(value).unwrap();
+        assert_eq!(config.patterns, vec!["*.md"]);
+        assert_eq!(config.rules.skill_structure, Some(true));
+    }
+
+    #[test]
+    fn test_parse_package_json_without_key() {
+        let json = r#"{ "name": "my-skills" }"#;
+        let result = format::parse_value(Path::new("package.json"), json);
+        assert!(matches!(result, Err(LintError::PackageJsonKey(..))));
+    }
+
+    #[test]
+    fn test_locate_searches_parent_directories() {
+        let start = Path::new("../.github/skills/foo").canonicalize().unwrap();
+        let found = locate(&start).unwrap();
+        let expected = Path::new("../.skills-lint.config.json").canonicalize().unwrap();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_load_locates_type_errors() {
+        let dir = std::env::temp_dir().join(format!("skills-lint-config-type-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(".skills-lint.config.json");
+        let content = r#"{
+  "patterns": ["*.md"],
+  "rules": { "token-limit": { "models": { "gpt-4o": { "warning": 8000, "error": 16000 } } } },
+  "overrides": [
+    { "files": ["a.md"], "rules": { "token-limit": { "models": { "gpt-4o": { "warning": true, "error": 10 } } } } }
+  ]
+}"#;
+        std::fs::write(&path, content).unwrap();
+        let result = Config::load_with(&path, &ConfigFlags::default());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let Err(LintError::ConfigInvalid(issues)) = result else {
+            panic!("expected a config issue, got {result:?}");
+        };
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.path.join("."), "overrides.0.rules.token-limit.models.gpt-4o.warning");
+        assert_eq!(issue.file, path.display().to_string());
+        assert_eq!(issue.location, Some((5, 79)));
+        assert!(issue.message.contains("invalid type"), "{}", issue.message);
+    }
+
+    #[test]
+    fn test_extends_preset_with_local_overrides() {
+        let mut value = presets::preset("recommended").unwrap();
+        extends::merge(
+            &mut value,
+            serde_json::json!({
+                "patterns": ["./skills/**/SKILL.md"],
+                "rules": {
+                    "token-limit": { "models": { "gpt-4o": { "error": 20000 } } },
+                    "unique-description": false
+                }
+            }),
+        );
+        let config: Config = serde_json::from_value(value).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.patterns, vec!["./skills/**/SKILL.md"]);
+        let budget = config.resolve_token_limit("skills/a/SKILL.md", "gpt-4o").unwrap();
+        assert_eq!(budget.warning, 8000);
+        assert_eq!(budget.error, 20000);
+        assert_eq!(config.rules.unique_description, Some(false));
+        assert!(config.resolve_token_limit("skills/a/SKILL.md", "gpt-4").is_some());
+    }
+
+    #[test]
+    fn test_from_value_resolves_presets_only() {
+        let config = Config::from_value(serde_json::json!({
+            "extends": "skills-lint:recommended",
+            "patterns": ["./skills/**/SKILL.md"]
+        }))
+        .unwrap();
+        assert_eq!(config.root, PathBuf::from("."));
+        assert_eq!(config.rules.skill_structure, Some(true));
+
+        let err = Config::from_value(serde_json::json!({ "extends": "./base.json", "patterns": ["*.md"] }));
+        assert!(matches!(err, Err(LintError::ConfigExtends(ref name, _)) if name == INLINE_CONFIG));
+        let err = Config::from_value(serde_json::json!({ "patterns": ["*.md"], "rulez": {} }));
+        assert!(matches!(err, Err(LintError::ConfigInvalid(_))));
+    }
+
+    #[test]
+    fn test_parse_yaml_content() {
+        let config = Config::parse("patterns: ['*.md']\nrules:\n  skill-structure: true\n", ConfigFormat::Yaml).unwrap();
+        assert_eq!(config.patterns, vec!["*.md"]);
+        assert!(matches!(
+            Config::parse("patterns: [", ConfigFormat::Yaml),
+            Err(LintError::ConfigParse(..))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_model_returns_none() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4": { "warning": 8000, "error": 12000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.resolve_token_limit("foo.md", "unknown-model").is_none());
+    }
+
+    #[test]
+    fn test_default_encoding_lookup() {
+        assert_eq!(default_encoding("gpt-5"), Some("o200k_base"));
+        assert_eq!(default_encoding("gpt-4o"), Some("o200k_base"));
+        assert_eq!(default_encoding("gpt-4o-mini"), Some("o200k_base"));
+        assert_eq!(default_encoding("gpt-4"), Some("cl100k_base"));
+        assert_eq!(default_encoding("gpt-4-turbo"), Some("cl100k_base"));
+        assert_eq!(default_encoding("gpt-3.5-turbo"), Some("cl100k_base"));
+        assert_eq!(default_encoding("unknown"), None);
+    }
+
+    #[test]
+    fn test_validate_rejects_unsupported_model() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "not-a-real-model": { "warning": 8000, "error": 12000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_with_skill_index_budget() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4o": { "warning": 8000, "error": 16000 }
+                    }
+                },
+                "skill-index-budget": {
+                    "models": {
+                        "gpt-4o": { "warning": 2000, "error": 4000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.rules.skill_index_budget.is_some());
+        let sib = config.rules.skill_index_budget.as_ref().unwrap();
+        assert_eq!(sib.models.get("gpt-4o").unwrap().warning, Budget::Tokens(2000));
+        assert_eq!(sib.models.get("gpt-4o").unwrap().error, Budget::Tokens(4000));
+    }
+
+    #[test]
+    fn test_parse_without_skill_index_budget() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4": { "warning": 8000, "error": 12000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.rules.skill_index_budget.is_none());
+    }
+
+    #[test]
+    fn test_resolve_skill_index_budget() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4o": { "warning": 8000, "error": 16000 }
+                    }
+                },
+                "skill-index-budget": {
+                    "models": {
+                        "gpt-4o": { "warning": 2000, "error": 4000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let budget = config.resolve_skill_index_budget("gpt-4o").unwrap();
+        assert_eq!(budget.warning, 2000);
+        assert_eq!(budget.error, 4000);
+        assert_eq!(budget.encoding, "o200k_base");
+    }
+
+    #[test]
+    fn test_resolve_skill_index_budget_unconfigured() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4": { "warning": 8000, "error": 12000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.resolve_skill_index_budget("gpt-4").is_none());
+    }
+
+    #[test]
+    fn test_parse_with_skill_structure() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4o": { "warning": 8000, "error": 16000 }
+                    }
+                },
+                "skill-structure": true
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.rules.skill_structure, Some(true));
+    }
+
+    #[test]
+    fn test_parse_without_skill_structure() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4": { "warning": 8000, "error": 12000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.rules.skill_structure.is_none());
+    }
+
+    #[test]
+    fn test_parse_skill_structure_false() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4o": { "warning": 8000, "error": 16000 }
+                    }
+                },
+                "skill-structure": false
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.rules.skill_structure, Some(false));
+    }
+
+    #[test]
+    fn test_validate_rejects_unsupported_model_in_skill_index_budget() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4": { "warning": 8000, "error": 12000 }
+                    }
+                },
+                "skill-index-budget": {
+                    "models": {
+                        "not-a-real-model": { "warning": 2000, "error": 4000 }
+                    }
+                }
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_structure_only_config() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "skill-structure": true,
+                "unique-name": true
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.rules.token_limit.is_none());
+        assert!(config.validate().is_ok());
+        assert!(config.resolve_token_limit("foo.md", "gpt-4").is_none());
+    }
+
+    #[test]
+    fn test_parse_ignore_patterns() {
+        let json = r#"{
+            "patterns": ["./**/SKILL.md"],
+            "ignorePatterns": ["node_modules/", "fixtures/**"],
+            "rules": { "skill-structure": true }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.ignore_patterns, vec!["node_modules/", "fixtures/**"]);
+        assert!(config.respect_ignore_files);
+    }
+
+    #[test]
+    fn test_validate_rejects_no_rules() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "skill-structure": false
+            }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(matches!(config.validate(), Err(LintError::NoRulesEnabled)));
+
+        let config: Config = serde_json::from_str(r#"{ "patterns": ["*.md"], "rules": { "size-limit": {} } }"#).unwrap();
+        assert!(matches!(config.validate(), Err(LintError::NoRulesEnabled)));
+
+        let json = r#"{ "patterns": ["*.md"], "rules": { "size-limit": {}, "skill-structure": true } }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(matches!(config.validate(), Err(LintError::ConfigInvalid(_))));
+
+        for rule in ["token-limit", "skill-index-budget", "frontmatter-limit"] {
+            let json = format!(r#"{{ "patterns": ["*.md"], "rules": {{ "{rule}": {{ "models": {{}} }} }} }}"#);
+            let config: Config = serde_json::from_str(&json).unwrap();
+            assert!(matches!(config.validate(), Err(LintError::NoRulesEnabled)), "{rule}");
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use serde_json::{json, Map, Value};
+
+use crate::config::supported_model_names;
+
+/// Prefix marking a built-in preset in `extends`, e.g. `skills-lint:recommended`.
+pub const PRESET_PREFIX: &str = "skills-lint:";
+
+/// Names of the built-in presets.
+pub const PRESET_NAMES: &[&str] = &["recommended", "strict"];
+
+/// Default pattern used by presets and `skills-lint init`.
+pub const DEFAULT_PATTERN: &str = "./.github/**/SKILL.md";
+
+/// Recommended `(warning, error)` budgets for one model, per token rule.
+pub struct ModelBudgets {
+    pub token_limit: (usize, usize),
+    pub frontmatter_limit: (usize, usize),
+    pub skill_index_budget: (usize, usize),
+}
+
+/// Return the recommended budgets for a model, falling back to the `gpt-4o` tier.
+pub fn default_budgets(model: &str) -> ModelBudgets {
+    match model {
+        "gpt-5" => ModelBudgets {
+            token_limit: (16000, 32000),
+            frontmatter_limit: (2000, 4000),
+            skill_index_budget: (4000, 8000),
+        },
+        "gpt-4o" | "gpt-4o-mini" | "gpt-4-turbo" => ModelBudgets {
+            token_limit: (8000, 16000),
+            frontmatter_limit: (1000, 2000),
+            skill_index_budget: (2000, 4000),
+        },
+        "gpt-4" => ModelBudgets {
+            token_limit: (2000, 4000),
+            frontmatter_limit: (500, 1000),
+            skill_index_budget: (1000, 2000),
+        },
+        "gpt-3.5-turbo" => ModelBudgets {
+            token_limit: (4000, 8000),
+            frontmatter_limit: (500, 1000),
+            skill_index_budget: (1000, 2000),
+        },
+        _ => ModelBudgets {
+            token_limit: (8000, 16000),
+            frontmatter_limit: (1000, 2000),
+            skill_index_budget: (2000, 4000),
+        },
+    }
+}
+
+/// Build a `{ "models": { ... } }` rule value from the recommended budgets.
+pub fn build_model_budgets(models: &[&str], extract: fn(&ModelBudgets) -> (usize, usize)) -> Value {
+    let mut model_map = Map::new();
+    for &model in models {
+        let budgets = default_budgets(model);
+        let (warning, error) = extract(&budgets);
+        model_map.insert(
+            model.to_string(),
+            json!({
+                "warning": warning,
+                "error": error,
+            }),
+        );
+    }
+    json!({ "models": model_map })
+}
+
+/// Return the raw config value of a built-in preset, or None if unknown.
+///
+/// `recommended` uses the recommended budgets for every supported model and enables
+/// all structural rules. `strict` errors where `recommended` warns and warns at half that.
+pub fn preset(name: &str) -> Option<Value> {
+    let tighten: fn((usize, usize)) -> (usize, usize) = match name {
+        "recommended" => |budget| budget,
+        "strict" => |(warning, _)| (warning / 2, warning),
+        _ => return None,
+    };
+
+    let models = supported_model_names();
+    let mut token_limit = Map::new();
+    let mut frontmatter_limit = Map::new();
+    let mut skill_index_budget = Map::new();
+    for &model in &models {
+        let budgets = default_budgets(model);
+        for (map, budget) in [
+            (&mut token_limit, budgets.token_limit),
+            (&mut frontmatter_limit, budgets.frontmatter_limit),
+            (&mut skill_index_budget, budgets.skill_index_budget),
+        ] {
+            let (warning, error) = tighten(budget);
+            map.insert(model.to_string(), json!({ "warning": warning, "error": error }));
+        }
+    }
+
+    Some(json!({
+        "patterns": [DEFAULT_PATTERN],
+        "rules": {
+            "token-limit": { "models": token_limit },
+            "frontmatter-limit": { "models": frontmatter_limit },
+            "skill-index-budget": { "models": skill_index_budget },
+            "skill-structure": true,
+            "unique-name": true,
+            "unique-description": true,
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommended_mirrors_default_budgets() {
+        let preset = preset("recommended").unwrap();
+        let gpt4o = &preset["rules"]["token-limit"]["models"]["gpt-4o"];
+        assert_eq!(gpt4o["warning"], 8000);
+        assert_eq!(gpt4o["error"], 16000);
+        assert_eq!(preset["rules"]["skill-structure"], true);
+    }
+
+    #[test]
+    fn test_strict_errors_at_recommended_warning() {
+        let preset = preset("strict").unwrap();
+        let gpt4 = &preset["rules"]["frontmatter-limit"]["models"]["gpt-4"];
+        assert_eq!(gpt4["warning"], 250);
+        assert_eq!(gpt4["error"], 500);
+    }
+
+    #[test]
+    fn test_unknown_preset() {
+        assert!(preset("lenient").is_none());
+    }
+}
@@ -0,0 +1,115 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+
+use crate::config::extends::EXTENDS_KEY;
+use crate::config::presets::{PRESET_NAMES, PRESET_PREFIX};
+use crate::config::{supported_model_names, Config};
+use crate::tokenizer::ENCODINGS;
+
+/// Public URL of the published schema, for use as the `$schema` of a config file.
+pub const SCHEMA_URL: &str = "https://haasstefan.github.io/skills-lint/schema.json";
+
+/// Generate the JSON Schema describing a config file.
+///
+/// The schema is derived from the [`Config`] types. `extends`, which is resolved before
+/// the config is deserialized, is added by hand.
+pub fn config_schema() -> Value {
+    let schema = schemars::schema_for!(Config);
+    let mut value = serde_json::to_value(schema).expect("schema serializes to JSON");
+
+    let presets: Vec<String> = PRESET_NAMES.iter().map(|name| format!("{PRESET_PREFIX}{name}")).collect();
+    let entry = json!({
+        "type": "string",
+        "anyOf": [{ "enum": presets }, { "type": "string" }]
+    });
+    value["$id"] = json!(SCHEMA_URL);
+    value["properties"][EXTENDS_KEY] = json!({
+        "description": "Built-in presets or config files (relative to this file) to inherit from.",
+        "anyOf": [entry.clone(), { "type": "array", "items": entry }]
+    });
+    value
+}
+
+/// Render the config schema as pretty-printed JSON with a trailing newline.
+pub fn config_schema_string() -> String {
+    let mut out = serde_json::to_string_pretty(&config_schema()).expect("schema serializes to JSON");
+    out.push('\n');
+    out
+}
+
+/// Schema for a `models` map: one optional `T` per supported model name.
+pub(crate) fn model_map<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    let budget = serde_json::to_value(gen.subschema_for::<T>()).expect("schema serializes to JSON");
+    let properties: Map<String, Value> = supported_model_names()
+        .into_iter()
+        .map(|model| (model.to_string(), budget.clone()))
+        .collect();
+    from_json(json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false
+    }))
+}
+
+/// Schema for an optional tokenizer encoding name.
+pub(crate) fn encoding(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "description": "Tokenizer encoding. Defaults to the model's encoding.",
+        "type": ["string", "null"],
+        "enum": ENCODINGS.iter().map(|e| Value::from(*e)).chain([Value::Null]).collect::<Vec<_>>()
+    }))
+}
+
+fn from_json(value: Value) -> Schema {
+    serde_json::from_value(value).expect("valid schema")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Committed copy of the schema, published with the website.
+    const PUBLISHED_SCHEMA: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../website/public/schema.json");
+
+    #[test]
+    fn test_published_schema_is_up_to_date() {
+        let published = std::fs::read_to_string(PUBLISHED_SCHEMA).unwrap_or_default();
+        assert!(
+            published == config_schema_string(),
+            "website/public/schema.json is out of date; regenerate it with `skills-lint schema > website/public/schema.json`"
+        );
+    }
+
+    #[test]
+    fn test_schema_lists_models_and_encodings() {
+        let schema = config_schema();
+        let defs = &schema["definitions"];
+        let models = &defs["TokenLimitConfig"]["properties"]["models"];
+        assert_eq!(models["additionalProperties"], false);
+        for model in supported_model_names() {
+            assert!(models["properties"].get(model).is_some(), "missing model {model}");
+        }
+        let encodings = &defs["ModelBudget"]["properties"]["encoding"]["enum"];
+        assert!(encodings.as_array().unwrap().contains(&json!("o200k_base")));
+    }
+
+    #[test]
+    fn test_schema_covers_top_level_keys() {
+        let schema = config_schema();
+        for key in crate::config::validate::TOP_LEVEL_KEYS {
+            assert!(schema["properties"].get(key).is_some(), "missing key {key}");
+        }
+        assert_eq!(schema["additionalProperties"], false);
+    }
+
+    #[test]
+    fn test_schema_accepts_repo_config_shape() {
+        let schema = config_schema();
+        let rules = &schema["definitions"]["RulesConfig"]["properties"];
+        for rule in crate::config::validate::RULE_NAMES {
+            assert!(rules.get(rule).is_some(), "missing rule {rule}");
+        }
+    }
+}
@@ -0,0 +1,613 @@
+use serde_json::{Map, Value};
+
+use crate::config::budget::Budget;
+use crate::config::{max_input, supported_model_names, Config, ModelBudget, SizeMetric};
+use crate::errors::ConfigIssue;
+use crate::tokenizer::ENCODINGS;
+
+/// Keys allowed at the top level of a config file.
+pub const TOP_LEVEL_KEYS: &[&str] = &[
+    "$schema",
+    "extends",
+    "patterns",
+    "ignorePatterns",
+    "respectIgnoreFiles",
+    "rules",
+    "overrides",
+    "cache",
+    "cacheLocation",
+    "pricing",
+];
+
+/// Names of all rules.
+pub const RULE_NAMES: &[&str] = &[
+    "token-limit",
+    "frontmatter-limit",
+    "skill-index-budget",
+    "skill-structure",
+    "unique-name",
+    "unique-description",
+    "size-limit",
+];
+
+/// Rules configured with a `models` map of token budgets.
+pub const TOKEN_RULES: &[&str] = &["token-limit", "frontmatter-limit", "skill-index-budget"];
+
+/// Rules that can be set in an `overrides` entry.
+const OVERRIDE_RULE_NAMES: &[&str] = &["token-limit", "size-limit"];
+
+const TOKEN_RULE_KEYS: &[&str] = &["models"];
+
+/// Fields of a per-model budget.
+pub const MODEL_BUDGET_KEYS: &[&str] = &["encoding", "warning", "error"];
+
+/// Metrics of the `size-limit` rule.
+pub const SIZE_LIMIT_KEYS: &[&str] = &["maxBytes", "maxLines", "maxWords"];
+
+/// Fields of a size-limit metric budget.
+pub const SIZE_BUDGET_KEYS: &[&str] = &["warning", "error"];
+
+const PRICE_KEYS: &[&str] = &["input"];
+
+const OVERRIDE_KEYS: &[&str] = &["files", "rules"];
+
+/// Check a raw config value for keys that are not part of the config format.
+///
+/// Values of the wrong type are left to deserialization to report.
+pub fn unknown_keys(value: &Value) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let Some(top) = value.as_object() else {
+        return issues;
+    };
+
+    check_keys(top, TOP_LEVEL_KEYS, &[], "field", &mut issues);
+
+    if let Some(rules) = top.get("rules").and_then(Value::as_object) {
+        check_rules(rules, RULE_NAMES, &path(&["rules"]), &mut issues);
+    }
+
+    if let Some(pricing) = top.get("pricing").and_then(Value::as_object) {
+        let pricing_path = path(&["pricing"]);
+        check_keys(pricing, &supported_model_names(), &pricing_path, "model", &mut issues);
+        for (model, price) in pricing {
+            if let Some(price) = price.as_object() {
+                check_keys(price, PRICE_KEYS, &extend(&pricing_path, model), "price field", &mut issues);
+            }
+        }
+    }
+
+    if let Some(overrides) = top.get("overrides").and_then(Value::as_array) {
+        for (i, entry) in overrides.iter().enumerate() {
+            let Some(entry) = entry.as_object() else {
+                continue;
+            };
+            let entry_path = path(&["overrides", &i.to_string()]);
+            check_keys(entry, OVERRIDE_KEYS, &entry_path, "override field", &mut issues);
+            if let Some(rules) = entry.get("rules").and_then(Value::as_object) {
+                let rules_path = extend(&entry_path, "rules");
+                check_rules(rules, OVERRIDE_RULE_NAMES, &rules_path, &mut issues);
+            }
+        }
+    }
+
+    issues
+}
+
+fn check_rules(rules: &Map<String, Value>, allowed: &[&str], rules_path: &[String], issues: &mut Vec<ConfigIssue>) {
+    check_keys(rules, allowed, rules_path, "rule", issues);
+
+    if let Some(size_limit) = rules.get("size-limit").and_then(Value::as_object) {
+        let rule_path = extend(rules_path, "size-limit");
+        check_keys(size_limit, SIZE_LIMIT_KEYS, &rule_path, "metric", issues);
+        for (metric, budget) in size_limit {
+            if let Some(budget) = budget.as_object() {
+                check_keys(budget, SIZE_BUDGET_KEYS, &extend(&rule_path, metric), "budget field", issues);
+            }
+        }
+    }
+
+    let models = supported_model_names();
+    for &rule in TOKEN_RULES {
+        let Some(rule_config) = rules.get(rule).and_then(Value::as_object) else {
+            continue;
+        };
+        let rule_path = extend(rules_path, rule);
+        check_keys(rule_config, TOKEN_RULE_KEYS, &rule_path, "field", issues);
+
+        let Some(model_map) = rule_config.get("models").and_then(Value::as_object) else {
+            continue;
+        };
+        let models_path = extend(&rule_path, "models");
+        check_keys(model_map, &models, &models_path, "model", issues);
+        for (model, budget) in model_map {
+            if let Some(budget) = budget.as_object() {
+                check_keys(budget, MODEL_BUDGET_KEYS, &extend(&models_path, model), "budget field", issues);
+            }
+        }
+    }
+}
+
+/// Report every key of `map` not in `allowed`, with a suggestion when one is close.
+fn check_keys(
+    map: &Map<String, Value>,
+    allowed: &[&str],
+    parent: &[String],
+    kind: &str,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    for key in map.keys() {
+        if allowed.contains(&key.as_str()) {
+            continue;
+        }
+        let location = if parent.is_empty() {
+            "at the top level".to_string()
+        } else {
+            format!("in \"{}\"", parent.join("."))
+        };
+        let mut message = format!("unknown {kind} \"{key}\" {location}");
+        match suggest(key, allowed) {
+            Some(suggestion) => message.push_str(&format!(" (did you mean \"{suggestion}\"?)")),
+            None => message.push_str(&format!(". Expected one of: {}", allowed.join(", "))),
+        }
+        issues.push(ConfigIssue::new(extend(parent, key), message));
+    }
+}
+
+/// Return the candidate closest to `input`, if it is close enough to be a likely typo.
+pub fn suggest<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|c| (strsim::jaro_winkler(input, c), *c))
+        .filter(|(score, _)| *score >= 0.8)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, c)| c)
+}
+
+/// Check the parsed config for values that are well-formed but inconsistent.
+pub fn semantic(config: &Config) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
+    if config.patterns.is_empty() {
+        issues.push(ConfigIssue::new(
+            path(&["patterns"]),
+            "\"patterns\" must contain at least one glob pattern",
+        ));
+    }
+
+    let rules = &config.rules;
+    let token_rules = [
+        ("token-limit", rules.token_limit.as_ref().map(|r| &r.models)),
+        ("frontmatter-limit", rules.frontmatter_limit.as_ref().map(|r| &r.models)),
+        ("skill-index-budget", rules.skill_index_budget.as_ref().map(|r| &r.models)),
+    ];
+    for (rule, models) in token_rules {
+        let Some(models) = models else {
+            continue;
+        };
+        let mut names: Vec<&String> = models.keys().collect();
+        names.sort();
+        for model in names {
+            let budget_path = path(&["rules", rule, "models", model]);
+            check_budget(model, &models[model], &budget_path, &mut issues);
+        }
+    }
+
+    let mut priced: Vec<&String> = config.pricing.keys().collect();
+    priced.sort();
+    for model in priced {
+        let price = config.pricing[model].input;
+        if !(price.is_finite() && price >= 0.0) {
+            issues.push(ConfigIssue::new(
+                path(&["pricing", model, "input"]),
+                format!("\"pricing.{model}.input\": price must be a non-negative number, got {price}"),
+            ));
+        }
+    }
+
+    if let Some(ref size_limit) = rules.size_limit {
+        if !size_limit.has_metrics() {
+            issues.push(ConfigIssue::new(
+                path(&["rules", "size-limit"]),
+                format!(
+                    "\"rules.size-limit\" must configure at least one of {}",
+                    SIZE_LIMIT_KEYS.join(", ")
+                ),
+            ));
+        }
+        for metric in SizeMetric::ALL {
+            if let Some(budget) = size_limit.get(metric) {
+                let budget_path = path(&["rules", "size-limit", metric.key()]);
+                check_size_budget(budget.warning, budget.error, &budget_path, &mut issues);
+            }
+        }
+    }
+
+    for (i, entry) in config.overrides.iter().enumerate() {
+        if let Some(ref size_limit) = entry.rules.size_limit {
+            for metric in SizeMetric::ALL {
+                let Some(ovr) = size_limit.get(metric) else {
+                    continue;
+                };
+                let budget_path = path(&["overrides", &i.to_string(), "rules", "size-limit", metric.key()]);
+                let Some(global) = rules.size_limit.as_ref().and_then(|sl| sl.get(metric)) else {
+                    issues.push(ConfigIssue::new(
+                        budget_path,
+                        format!(
+                            "override for \"{}\" has no effect: it is not configured under \"rules.size-limit\"",
+                            metric.key()
+                        ),
+                    ));
+                    continue;
+                };
+                let warning = ovr.warning.unwrap_or(global.warning);
+                let error = ovr.error.unwrap_or(global.error);
+                check_size_budget(warning, error, &budget_path, &mut issues);
+            }
+        }
+
+        let Some(ref token_limit) = entry.rules.token_limit else {
+            continue;
+        };
+        let mut names: Vec<&String> = token_limit.models.keys().collect();
+        names.sort();
+        for model in names {
+            let budget_path = path(&["overrides", &i.to_string(), "rules", "token-limit", "models", model]);
+            let ovr = &token_limit.models[model];
+            let Some(global) = rules.token_limit.as_ref().and_then(|tl| tl.models.get(model)) else {
+                issues.push(ConfigIssue::new(
+                    budget_path,
+                    format!("override for model \"{model}\" has no effect: \"{model}\" is not configured under \"rules.token-limit\""),
+                ));
+                continue;
+            };
+            let effective = ModelBudget {
+                encoding: ovr.encoding.clone().or_else(|| global.encoding.clone()),
+                warning: ovr.warning.unwrap_or(global.warning),
+                error: ovr.error.unwrap_or(global.error),
+            };
+            check_budget(model, &effective, &budget_path, &mut issues);
+        }
+    }
+
+    issues
+}
+
+fn check_budget(model: &str, budget: &ModelBudget, budget_path: &[String], issues: &mut Vec<ConfigIssue>) {
+    let display = budget_path.join(".");
+    let max = max_input(model).unwrap_or_default();
+    if budget.warning.resolve(max) > budget.error.resolve(max) {
+        issues.push(ConfigIssue::new(
+            extend(budget_path, "warning"),
+            format!(
+                "\"{display}\": warning ({}) must not be greater than error ({})",
+                describe(budget.warning, max),
+                describe(budget.error, max)
+            ),
+        ));
+    }
+    if let Some(ref encoding) = budget.encoding {
+        if !ENCODINGS.contains(&encoding.as_str()) {
+            let mut message = format!("\"{display}\": unknown encoding \"{encoding}\"");
+            match suggest(encoding, ENCODINGS) {
+                Some(suggestion) => message.push_str(&format!(" (did you mean \"{suggestion}\"?)")),
+                None => message.push_str(&format!(". Expected one of: {}", ENCODINGS.join(", "))),
+            }
+            issues.push(ConfigIssue::new(extend(budget_path, "encoding"), message));
+        }
+    }
+}
+
+fn check_size_budget(warning: usize, error: usize, budget_path: &[String], issues: &mut Vec<ConfigIssue>) {
+    if warning > error {
+        issues.push(ConfigIssue::new(
+            extend(budget_path, "warning"),
+            format!(
+                "\"{}\": warning ({warning}) must not be greater than error ({error})",
+                budget_path.join(".")
+            ),
+        ));
+    }
+}
+
+/// Show a budget as written, adding the resolved token count for percentages.
+fn describe(budget: Budget, max: usize) -> String {
+    match budget {
+        Budget::Tokens(_) => budget.to_string(),
+        Budget::Percent(_) => format!("{budget} = {} tokens", budget.resolve(max)),
+    }
+}
+
+/// Turn a deserialization error into an issue at the key path of the offending value.
+///
+/// Returns `None` if the error is not tied to a key, e.g. when the config is not an object.
+pub fn type_error(err: &serde_path_to_error::Error<serde_json::Error>) -> Option<ConfigIssue> {
+    use serde_path_to_error::Segment;
+    let key_path: Vec<String> = err
+        .path()
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(key.clone()),
+            Segment::Enum { variant } => Some(variant.clone()),
+            Segment::Unknown => None,
+        })
+        .collect();
+    if key_path.is_empty() {
+        return None;
+    }
+    let message = format!("\"{}\": {}", key_path.join("."), err.inner());
+    Some(ConfigIssue::new(key_path, message))
+}
+
+/// Attach the source file and the best-effort location of each issue's key within `content`.
+pub fn attach_source(issues: &mut [ConfigIssue], file: &str, content: &str) {
+    for issue in issues {
+        issue.file = file.to_string();
+        issue.location = locate(content, &issue.path);
+    }
+}
+
+/// Attach the source file and location to the issues whose value was set by the file
+/// itself, i.e. whose value in the `merged` config is the one in `own`. Issues caused by a
+/// base config or a command-line flag are left without a location.
+pub fn attach_own_source(issues: &mut [ConfigIssue], file: &str, content: &str, own: &Value, merged: &Value) {
+    for issue in issues {
+        if is_own_value(&issue.path, own, merged) {
+            issue.file = file.to_string();
+            issue.location = locate(content, &issue.path);
+        }
+    }
+}
+
+/// Returns true if the value at `key_path` in `merged` is set by `own`.
+///
+/// Bases' `overrides` entries come first in the merged list, so an entry's index in `own`
+/// is its merged index minus the number of inherited entries.
+fn is_own_value(key_path: &[String], own: &Value, merged: &Value) -> bool {
+    let mut own_path = key_path.to_vec();
+    if let (Some("overrides"), Some(index)) = (key_path.first().map(String::as_str), key_path.get(1)) {
+        let count = |value: &Value| value.get("overrides").and_then(Value::as_array).map_or(0, Vec::len);
+        let Some(own_index) = index
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| i.checked_sub(count(merged).saturating_sub(count(own))))
+        else {
+            return false;
+        };
+        own_path[1] = own_index.to_string();
+    }
+    match (lookup(own, &own_path), lookup(merged, key_path)) {
+        (Some(own), Some(merged)) => own == merged,
+        _ => false,
+    }
+}
+
+fn lookup<'v>(value: &'v Value, key_path: &[String]) -> Option<&'v Value> {
+    key_path.iter().try_fold(value, |value, segment| match value {
+        Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+        _ => value.get(segment),
+    })
+}
+
+/// Find the 1-based line and column of the key at `key_path` in a JSON, YAML or TOML source.
+///
+/// Each key is searched for after the position of its parent, so the result is a
+/// best-effort location that falls back to the nearest parent found.
+pub fn locate(content: &str, key_path: &[String]) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    let mut found = None;
+    for segment in key_path {
+        // Array indices do not appear in the source.
+        if segment.parse::<usize>().is_ok() {
+            continue;
+        }
+        match find_key(content, offset, segment) {
+            Some(pos) => {
+                found = Some(pos);
+                offset = pos + segment.len();
+            }
+            None => break,
+        }
+    }
+    found.map(|pos| line_column(content, pos))
+}
+
+/// Find the next occurrence of `key` at or after `from` that looks like a key rather than a substring.
+fn find_key(content: &str, from: usize, key: &str) -> Option<usize> {
+    let is_before = |c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '.' | '[' | '{' | ',');
+    let is_after = |c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ':' | '=' | '.' | ']');
+    content.get(from..)?.match_indices(key).find_map(|(i, _)| {
+        let start = from + i;
+        let end = start + key.len();
+        let before_ok = content[..start].chars().next_back().is_none_or(is_before);
+        let after_ok = content[end..].chars().next().is_none_or(is_after);
+        (before_ok && after_ok).then_some(start)
+    })
+}
+
+fn line_column(content: &str, pos: usize) -> (usize, usize) {
+    let before = &content[..pos];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    (line, column)
+}
+
+fn path(segments: &[&str]) -> Vec<String> {
+    segments.iter().map(|s| s.to_string()).collect()
+}
+
+fn extend(parent: &[String], segment: &str) -> Vec<String> {
+    let mut path = parent.to_vec();
+    path.push(segment.to_string());
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_unknown_rule_suggestion() {
+        let value = json!({
+            "patterns": ["*.md"],
+            "rules": { "skill-strucutre": true }
+        });
+        let issues = unknown_keys(&value);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, vec!["rules", "skill-strucutre"]);
+        assert!(issues[0].message.contains("did you mean \"skill-structure\""), "{}", issues[0].message);
+    }
+
+    #[test]
+    fn test_unknown_budget_field_and_model() {
+        let value = json!({
+            "patterns": ["*.md"],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4o": { "warnings": 100, "error": 200 },
+                        "gpt4o-mini": { "warning": 100, "error": 200 }
+                    }
+                }
+            },
+            "overrides": [{ "files": ["a.md"], "rules": { "frontmatter-limit": {} } }]
+        });
+        let messages: Vec<String> = unknown_keys(&value).into_iter().map(|i| i.message).collect();
+        assert!(messages.iter().any(|m| m.contains("\"warnings\"") && m.contains("did you mean \"warning\"")));
+        assert!(messages.iter().any(|m| m.contains("\"gpt4o-mini\"") && m.contains("did you mean \"gpt-4o-mini\"")));
+        assert!(messages.iter().any(|m| m.contains("unknown rule \"frontmatter-limit\" in \"overrides.0.rules\"")));
+    }
+
+    #[test]
+    fn test_pricing_checks() {
+        let value = json!({
+            "patterns": ["*.md"],
+            "rules": { "skill-structure": true },
+            "pricing": { "gpt4o": { "input": 2.5 }, "gpt-4": { "inputs": 30 } }
+        });
+        let messages: Vec<String> = unknown_keys(&value).into_iter().map(|i| i.message).collect();
+        assert!(messages.iter().any(|m| m.contains("\"gpt4o\"") && m.contains("did you mean \"gpt-4o\"")));
+        assert!(messages.iter().any(|m| m.contains("\"inputs\"") && m.contains("did you mean \"input\"")));
+
+        let config: Config = serde_json::from_value(json!({
+            "patterns": ["*.md"],
+            "rules": { "skill-structure": true },
+            "pricing": { "gpt-4o": { "input": -1 } }
+        }))
+        .unwrap();
+        let issues = semantic(&config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, vec!["pricing", "gpt-4o", "input"]);
+    }
+
+    #[test]
+    fn test_unknown_key_without_close_match_lists_expected() {
+        let value = json!({ "patterns": ["*.md"], "rules": {}, "zzz": 1 });
+        let issues = unknown_keys(&value);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("Expected one of: $schema, extends, patterns"));
+    }
+
+    #[test]
+    fn test_semantic_checks() {
+        let config: Config = serde_json::from_value(json!({
+            "patterns": [],
+            "rules": {
+                "token-limit": {
+                    "models": {
+                        "gpt-4o": { "warning": 9000, "error": 8000, "encoding": "o200k" }
+                    }
+                }
+            },
+            "overrides": [{
+                "files": ["a.md"],
+                "rules": { "token-limit": { "models": { "gpt-4": { "warning": 1 } } } }
+            }]
+        }))
+        .unwrap();
+        let messages: Vec<String> = semantic(&config).into_iter().map(|i| i.message).collect();
+        assert_eq!(messages.len(), 4, "{messages:?}");
+        assert!(messages[0].contains("at least one glob pattern"));
+        assert!(messages[1].contains("warning (9000) must not be greater than error (8000)"));
+        assert!(messages[2].contains("did you mean \"o200k_base\""));
+        assert!(messages[3].contains("not configured under \"rules.token-limit\""));
+    }
+
+    #[test]
+    fn test_semantic_checks_override_against_global() {
+        let config: Config = serde_json::from_value(json!({
+            "patterns": ["*.md"],
+            "rules": { "token-limit": { "models": { "gpt-4o": { "warning": 8000, "error": 16000 } } } },
+            "overrides": [{
+                "files": ["a.md"],
+                "rules": { "token-limit": { "models": { "gpt-4o": { "warning": 20000 } } } }
+            }]
+        }))
+        .unwrap();
+        let issues = semantic(&config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path.last().unwrap(), "warning");
+    }
+
+    #[test]
+    fn test_locate_json_key() {
+        let content = "{\n  \"patterns\": [\"*.md\"],\n  \"rules\": {\n    \"skill-strucutre\": true\n  }\n}";
+        let location = locate(content, &path(&["rules", "skill-strucutre"]));
+        assert_eq!(location, Some((4, 6)));
+    }
+
+    #[test]
+    fn test_locate_skips_substring_matches() {
+        let content = "{\n  \"rules\": {\n    \"token-limit\": { \"models\": {\n      \"gpt-4o-mini\": {},\n      \"gpt-4o\": {}\n    } }\n  }\n}";
+        let location = locate(content, &path(&["rules", "token-limit", "models", "gpt-4o"]));
+        assert_eq!(location, Some((5, 8)));
+    }
+
+    #[test]
+    fn test_locate_yaml_and_toml_keys() {
+        let yaml = "patterns: ['*.md']\nrules:\n  warnings: 1\n";
+        assert_eq!(locate(yaml, &path(&["rules", "warnings"])), Some((3, 3)));
+
+        let toml = "patterns = ['*.md']\n\n[rules.token-limit.models.gpt-4o]\nwarnings = 1\n";
+        let location = locate(toml, &path(&["rules", "token-limit", "models", "gpt-4o", "warnings"]));
+        assert_eq!(location, Some((4, 1)));
+    }
+
+    #[test]
+    fn test_attach_own_source_skips_inherited_values() {
+        let own = json!({
+            "rules": { "token-limit": { "models": { "gpt-4o": { "warning": 9000, "error": 8000 } } } },
+            "overrides": [{ "files": ["b.md"], "rules": { "size-limit": {} } }]
+        });
+        let mut merged = own.clone();
+        merged["patterns"] = json!([]);
+        merged["overrides"] = json!([{ "files": ["a.md"] }, own["overrides"][0]]);
+        let content = serde_json::to_string_pretty(&own).unwrap();
+
+        let mut issues = vec![
+            ConfigIssue::new(path(&["patterns"]), "inherited"),
+            ConfigIssue::new(path(&["rules", "token-limit", "models", "gpt-4o", "warning"]), "own"),
+            ConfigIssue::new(path(&["overrides", "0", "files"]), "inherited override"),
+            ConfigIssue::new(path(&["overrides", "1", "rules", "size-limit"]), "own override"),
+        ];
+        attach_own_source(&mut issues, "a.json", &content, &own, &merged);
+        let located: Vec<bool> = issues.iter().map(|i| i.location.is_some()).collect();
+        assert_eq!(located, vec![false, true, false, true]);
+        assert!(issues[0].file.is_empty());
+        assert_eq!(issues[1].file, "a.json");
+
+        merged["rules"]["token-limit"]["models"]["gpt-4o"]["warning"] = json!(9500);
+        let mut issues = vec![ConfigIssue::new(path(&["rules", "token-limit", "models", "gpt-4o", "warning"]), "flag")];
+        attach_own_source(&mut issues, "a.json", &content, &own, &merged);
+        assert_eq!(issues[0].location, None);
+    }
+
+    #[test]
+    fn test_suggest() {
+        assert_eq!(suggest("warnings", MODEL_BUDGET_KEYS), Some("warning"));
+        assert_eq!(suggest("skill-strucutre", RULE_NAMES), Some("skill-structure"));
+        assert_eq!(suggest("o200k", ENCODINGS), Some("o200k_base"));
+        assert_eq!(suggest("completely-different", RULE_NAMES), None);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::cache::Cache;
+use crate::config::{default_encoding, supported_model_names, Config};
+use crate::errors::LintError;
+use crate::rules::skill_index_budget;
+
+/// Token counts and prices for one priced model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelCost {
+    pub model: String,
+    /// USD per 1M input tokens.
+    pub price: f64,
+    /// Tokens of each file when the skill is loaded, in the order files were given.
+    pub file_tokens: Vec<usize>,
+    /// Tokens of the skill index that is loaded into every conversation, if estimated.
+    pub index_tokens: Option<usize>,
+}
+
+impl ModelCost {
+    /// Cost in USD of sending `tokens` input tokens.
+    pub fn cost(&self, tokens: usize) -> f64 {
+        tokens as f64 * self.price / 1_000_000.0
+    }
+
+    /// Tokens of loading every skill once.
+    pub fn total_tokens(&self) -> usize {
+        self.file_tokens.iter().sum()
+    }
+}
+
+/// Estimate input costs for every model in `pricing`, in the order of the model registry.
+/// `contents` holds the content of each file in `files`.
+///
+/// Files are counted with the encoding their `token-limit` budget uses, and the skill index
+/// with the `skill-index-budget` encoding, falling back to the model's default encoding.
+/// Pass `with_index = false` to skip the index, e.g. when linting a single file.
+pub fn estimate(
+    config: &Config,
+    files: &[String],
+    contents: &[String],
+    with_index: bool,
+    mut cache: Option<&mut (dyn Cache + '_)>,
+) -> Result<Vec<ModelCost>, LintError> {
+    let index = with_index.then(|| skill_index_budget::aggregate_frontmatter(contents));
+    let mut costs = Vec::new();
+    for model in supported_model_names() {
+        let Some(price) = config.pricing.get(model) else {
+            continue;
+        };
+        let fallback = default_encoding(model).unwrap_or_default();
+
+        let mut file_tokens = Vec::with_capacity(files.len());
+        for (file, content) in files.iter().zip(contents) {
+            let encoding = config
+                .resolve_token_limit(file, model)
+                .map(|budget| budget.encoding)
+                .unwrap_or_else(|| fallback.to_string());
+            file_tokens.push(count(content, &encoding, cache.as_deref_mut())?);
+        }
+
+        let index_tokens = match index {
+            Some(ref index) => {
+                let encoding = config
+                    .resolve_skill_index_budget(model)
+                    .map(|budget| budget.encoding)
+                    .unwrap_or_else(|| fallback.to_string());
+                Some(count(index, &encoding, cache.as_deref_mut())?)
+            }
+            None => None,
+        };
+
+        costs.push(ModelCost {
+            model: model.to_string(),
+            price: price.input,
+            file_tokens,
+            index_tokens,
+        });
+    }
+    Ok(costs)
+}
+
+fn count(text: &str, encoding: &str, cache: Option<&mut (dyn Cache + '_)>) -> Result<usize, LintError> {
+    match cache {
+        Some(cache) => cache.count_tokens(text, encoding),
+        None => crate::tokenizer::count_tokens(text, encoding),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_uses_configured_prices() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": { "skill-structure": true },
+            "pricing": { "gpt-4o": { "input": 2.5 }, "gpt-4": { "input": 30 } }
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let files = vec!["a.md".to_string(), "b.md".to_string()];
+        let contents = vec!["Hello world.".to_string(), "---\nname: a\n---\nHello world.".to_string()];
+
+        let costs = estimate(&config, &files, &contents, true, None).unwrap();
+        let models: Vec<&str> = costs.iter().map(|c| c.model.as_str()).collect();
+        assert_eq!(models, vec!["gpt-4o", "gpt-4"]);
+
+        let gpt4o = &costs[0];
+        assert_eq!(gpt4o.file_tokens, vec![3, 9]);
+        assert_eq!(gpt4o.total_tokens(), 12);
+        assert_eq!(gpt4o.index_tokens, Some(3));
+        assert_eq!(gpt4o.cost(1_000_000), 2.5);
+        assert_eq!(costs[1].cost(100_000), 3.0);
+
+        let costs = estimate(&config, &files, &contents, false, None).unwrap();
+        assert_eq!(costs[0].index_tokens, None);
+    }
+}
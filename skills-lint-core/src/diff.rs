@@ -0,0 +1,343 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::cache::Cache;
+use crate::config::{supported_model_names, Config};
+use crate::errors::LintError;
+use crate::lint::{self, Linter};
+use crate::source::SkillSource;
+use crate::types::{LintReport, Severity};
+
+/// Rule whose findings give the token count of each file.
+const FILE_TOKENS_RULE: &str = "token-limit";
+
+/// Rule whose findings give the token count of the skill index.
+const INDEX_TOKENS_RULE: &str = "skill-index-budget";
+
+/// The files of one tree, their content and their lint report.
+#[derive(Debug, Clone)]
+pub struct TreeLint {
+    pub files: Vec<String>,
+    pub contents: Vec<String>,
+    pub report: LintReport,
+}
+
+impl TreeLint {
+    /// Discover, read and lint the files of `source`.
+    pub fn lint(
+        config: &Config,
+        source: &dyn SkillSource,
+        cache: Option<&mut (dyn Cache + '_)>,
+    ) -> Result<Self, LintError> {
+        let files = lint::discover(config, source)?;
+        let contents = lint::read_files(source, &files)?;
+
+        let mut linter = Linter::new(config).sources(files.iter().cloned().zip(contents.iter().cloned()));
+        if let Some(cache) = cache {
+            linter = linter.cache(cache);
+        }
+        let report = linter.run()?;
+        Ok(Self { files, contents, report })
+    }
+}
+
+/// How a file differs between the two trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Token count of one model in the base and head trees. A side is `None` when the file
+/// does not exist there or no rule counted it for the model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TokenChange {
+    pub model: String,
+    pub base: Option<usize>,
+    pub head: Option<usize>,
+    /// Head minus base, counting a missing side as 0.
+    pub delta: i64,
+}
+
+impl TokenChange {
+    fn new(model: &str, base: Option<usize>, head: Option<usize>) -> Self {
+        Self {
+            model: model.to_string(),
+            base,
+            head,
+            delta: head.unwrap_or(0) as i64 - base.unwrap_or(0) as i64,
+        }
+    }
+}
+
+/// A file that was added, removed or modified, with its token changes per model.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileChange {
+    pub file: String,
+    pub status: FileStatus,
+    pub tokens: Vec<TokenChange>,
+}
+
+/// A finding whose severity changed, e.g. from pass to warning. A side is `None` when the
+/// finding does not exist there, e.g. for an added or removed file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Transition {
+    pub rule: String,
+    pub file: String,
+    /// Model or size metric, for budget rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub from: Option<Severity>,
+    pub to: Option<Severity>,
+}
+
+/// Differences between the lint results of two trees.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintDiff {
+    /// Models with token counts in either tree, in the order of the model registry.
+    pub models: Vec<String>,
+    /// Added, removed and modified files, by path.
+    pub files: Vec<FileChange>,
+    /// Tokens of the skill index per model, like `total`. Empty without `skill-index-budget`.
+    pub index: Vec<TokenChange>,
+    /// Tokens of all files together, one entry per model in `models`.
+    pub total: Vec<TokenChange>,
+    /// Findings whose severity changed, by file. Findings that only exist in one tree are
+    /// included unless they pass.
+    pub transitions: Vec<Transition>,
+}
+
+impl LintDiff {
+    /// Compare the results of linting `base` and `head` with the same config.
+    ///
+    /// File tokens come from `token-limit` findings and index tokens from
+    /// `skill-index-budget` findings, so they are only reported for the models those
+    /// rules check.
+    pub fn between(base: &TreeLint, head: &TreeLint) -> Self {
+        let base_tokens = token_counts(&base.report, FILE_TOKENS_RULE);
+        let head_tokens = token_counts(&head.report, FILE_TOKENS_RULE);
+        let base_index = token_counts(&base.report, INDEX_TOKENS_RULE);
+        let head_index = token_counts(&head.report, INDEX_TOKENS_RULE);
+
+        let counted: BTreeSet<&str> = [&base_tokens, &head_tokens, &base_index, &head_index]
+            .into_iter()
+            .flat_map(|counts| counts.keys().map(|(_, model)| *model))
+            .collect();
+        let models: Vec<&str> = supported_model_names()
+            .into_iter()
+            .filter(|model| counted.contains(model))
+            .collect();
+
+        let base_files: BTreeMap<&str, &String> = base.files.iter().map(String::as_str).zip(&base.contents).collect();
+        let head_files: BTreeMap<&str, &String> = head.files.iter().map(String::as_str).zip(&head.contents).collect();
+        let paths: BTreeSet<&str> = base_files.keys().chain(head_files.keys()).copied().collect();
+
+        let mut files = Vec::new();
+        for file in paths {
+            let status = match (base_files.get(file), head_files.get(file)) {
+                (Some(before), Some(after)) if before == after => continue,
+                (Some(_), Some(_)) => FileStatus::Modified,
+                (None, _) => FileStatus::Added,
+                (_, None) => FileStatus::Removed,
+            };
+            let tokens = models
+                .iter()
+                .map(|model| {
+                    let key = (file, *model);
+                    TokenChange::new(model, base_tokens.get(&key).copied(), head_tokens.get(&key).copied())
+                })
+                .collect();
+            files.push(FileChange {
+                file: file.to_string(),
+                status,
+                tokens,
+            });
+        }
+
+        let index = if base_index.is_empty() && head_index.is_empty() {
+            Vec::new()
+        } else {
+            models
+                .iter()
+                .map(|model| TokenChange::new(model, sum_model(&base_index, model), sum_model(&head_index, model)))
+                .collect()
+        };
+        let total = models
+            .iter()
+            .map(|model| TokenChange::new(model, sum_model(&base_tokens, model), sum_model(&head_tokens, model)))
+            .collect();
+
+        let before = severities(&base.report);
+        let after = severities(&head.report);
+        let keys: BTreeSet<_> = before.keys().chain(after.keys()).copied().collect();
+        let transitions = keys
+            .into_iter()
+            .filter_map(|key| {
+                let (from, to) = (before.get(&key).copied(), after.get(&key).copied());
+                let changed = match (from, to) {
+                    (Some(from), Some(to)) => from != to,
+                    // A finding appearing or disappearing only matters if it did not pass.
+                    (Some(severity), None) | (None, Some(severity)) => severity != Severity::Pass,
+                    (None, None) => false,
+                };
+                let (file, rule, model) = key;
+                changed.then(|| Transition {
+                    rule: rule.to_string(),
+                    file: file.to_string(),
+                    model: model.map(str::to_string),
+                    from,
+                    to,
+                })
+            })
+            .collect();
+
+        Self {
+            models: models.into_iter().map(str::to_string).collect(),
+            files,
+            index,
+            total,
+            transitions,
+        }
+    }
+
+    /// Whether any file, index count or severity changed.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.transitions.is_empty() && self.index.iter().all(|change| change.delta == 0)
+    }
+
+    /// Number of changed files with the given status.
+    pub fn count(&self, status: FileStatus) -> usize {
+        self.files.iter().filter(|change| change.status == status).count()
+    }
+}
+
+/// Token counts of the findings of `rule`, keyed by file and model.
+fn token_counts<'r>(report: &'r LintReport, rule: &str) -> BTreeMap<(&'r str, &'r str), usize> {
+    report
+        .findings
+        .iter()
+        .filter(|finding| finding.rule == rule)
+        .map(|finding| ((finding.file.as_str(), finding.model.as_str()), finding.token_count))
+        .collect()
+}
+
+/// Sum the counts of `model`, or None if there are none.
+fn sum_model(counts: &BTreeMap<(&str, &str), usize>, model: &str) -> Option<usize> {
+    counts
+        .iter()
+        .filter(|((_, m), _)| *m == model)
+        .map(|(_, tokens)| *tokens)
+        .reduce(|a, b| a + b)
+}
+
+/// Severity of every finding, keyed by file, rule and model.
+fn severities(report: &LintReport) -> BTreeMap<(&str, &str, Option<&str>), Severity> {
+    let findings = report
+        .findings
+        .iter()
+        .map(|f| ((f.file.as_str(), f.rule.as_str(), Some(f.model.as_str())), f.severity));
+    let structure = report
+        .structure_findings
+        .iter()
+        .map(|f| ((f.file.as_str(), f.rule.as_str(), None), f.severity));
+    findings.chain(structure).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::MemorySource;
+
+    fn config() -> Config {
+        serde_json::from_str(
+            r#"{
+                "patterns": ["skills/**/SKILL.md"],
+                "rules": {
+                    "token-limit": { "models": { "gpt-4o": { "warning": 20, "error": 100 } } },
+                    "skill-index-budget": { "models": { "gpt-4o": { "warning": 1000, "error": 2000 } } },
+                    "skill-structure": true
+                },
+                "cache": false
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn tree(files: &[(&str, &str)]) -> TreeLint {
+        let source: MemorySource = files.iter().copied().collect();
+        TreeLint::lint(&config(), &source, None).unwrap()
+    }
+
+    #[test]
+    fn test_diff_reports_file_changes_and_transitions() {
+        let skill = |body: &str| format!("---\nname: a\ndescription: A skill\n---\n\n{body}\n");
+        let base = tree(&[
+            ("skills/a/SKILL.md", &skill("Short.")),
+            ("skills/b/SKILL.md", &skill("Same.")),
+            ("skills/old/SKILL.md", &skill("Gone.")),
+        ]);
+        let head = tree(&[
+            ("skills/a/SKILL.md", &skill("A much longer body that goes well past the warning budget.")),
+            ("skills/b/SKILL.md", &skill("Same.")),
+            ("skills/new/SKILL.md", &skill("Fresh.")),
+        ]);
+
+        let diff = LintDiff::between(&base, &head);
+        assert_eq!(diff.models, vec!["gpt-4o"]);
+        let changes: Vec<(&str, FileStatus)> = diff.files.iter().map(|f| (f.file.as_str(), f.status)).collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("skills/a/SKILL.md", FileStatus::Modified),
+                ("skills/new/SKILL.md", FileStatus::Added),
+                ("skills/old/SKILL.md", FileStatus::Removed),
+            ]
+        );
+
+        let a = &diff.files[0].tokens[0];
+        assert!(a.delta > 0);
+        assert_eq!(a.delta, a.head.unwrap() as i64 - a.base.unwrap() as i64);
+        assert_eq!(diff.files[2].tokens[0].head, None);
+        assert_eq!(diff.index.len(), diff.models.len());
+        assert_eq!(diff.index[0].delta, 0);
+        let total = &diff.total[0];
+        assert_eq!(total.delta, diff.files.iter().map(|f| f.tokens[0].delta).sum::<i64>());
+
+        assert_eq!(
+            diff.transitions,
+            vec![Transition {
+                rule: "token-limit".to_string(),
+                file: "skills/a/SKILL.md".to_string(),
+                model: Some("gpt-4o".to_string()),
+                from: Some(Severity::Pass),
+                to: Some(Severity::Warning),
+            }]
+        );
+        assert_eq!(diff.count(FileStatus::Added), 1);
+        assert!(LintDiff::between(&base, &base).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_states_of_added_and_removed_files() {
+        let broken = "No frontmatter.\n";
+        let base = tree(&[("skills/old/SKILL.md", broken)]);
+        let head = tree(&[("skills/new/SKILL.md", broken)]);
+
+        let diff = LintDiff::between(&base, &head);
+        let states: Vec<(&str, &str, Option<Severity>, Option<Severity>)> = diff
+            .transitions
+            .iter()
+            .map(|t| (t.file.as_str(), t.rule.as_str(), t.from, t.to))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("skills/new/SKILL.md", "skill-structure", None, Some(Severity::Error)),
+                ("skills/old/SKILL.md", "skill-structure", Some(Severity::Error), None),
+            ]
+        );
+    }
+}
@@ -1,28 +1,245 @@
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
 use crate::errors::LintError;
+use crate::source::{SkillSource, WalkOptions};
+
+/// Ignore file honoured during discovery. Uses gitignore syntax.
+pub const IGNORE_FILE: &str = ".skills-lintignore";
+
+/// Match options mirroring `glob::glob`, where `*` never crosses a path separator.
+pub const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Discover files in `source` matching the given glob patterns, excluding any path matched
+/// by `ignore_patterns` or by the `.skills-lintignore` file at the root of `source`.
+///
+/// Patterns are resolved against the root of `source` and the returned paths are relative
+/// to it. Each pattern is walked from its literal base directory. When `respect_ignore_files`
+/// is set, the walk skips hidden entries and anything excluded by `.gitignore` or `.ignore`.
+pub fn discover_files(
+    source: &dyn SkillSource,
+    patterns: &[String],
+    ignore_patterns: &[String],
+    respect_ignore_files: bool,
+) -> Result<Vec<String>, LintError> {
+    let ignore = build_ignore(source, ignore_patterns)?;
 
-/// Discover files matching the given glob patterns.
-pub fn discover_files(patterns: &[String]) -> Result<Vec<String>, LintError> {
     let mut files = Vec::new();
     for pattern in patterns {
-        let paths = glob::glob(pattern)
-            .map_err(|e| LintError::GlobPattern(pattern.clone(), e.to_string()))?;
-        for entry in paths {
-            let path = entry.map_err(|e| LintError::GlobIteration(e.to_string()))?;
-            files.push(path.display().to_string());
-        }
+        files.extend(walk_pattern(source, pattern, &ignore, respect_ignore_files)?);
     }
     files.sort();
     files.dedup();
     Ok(files)
 }
 
+/// Walk the base directory of a single glob pattern and collect matching files.
+fn walk_pattern(
+    source: &dyn SkillSource,
+    pattern: &str,
+    ignore: &Gitignore,
+    respect_ignore_files: bool,
+) -> Result<Vec<String>, LintError> {
+    let pattern = normalize(pattern);
+    let matcher =
+        Pattern::new(pattern).map_err(|e| LintError::GlobPattern(pattern.to_string(), e.to_string()))?;
+    let (base, depth) = split_pattern(pattern);
+
+    // A pattern without wildcards names a single path.
+    if depth == Some(0) {
+        if source.is_file(pattern) && !is_ignored(ignore, Path::new(pattern)) {
+            return Ok(vec![pattern.to_string()]);
+        }
+        return Ok(Vec::new());
+    }
+
+    let options = WalkOptions {
+        max_depth: depth,
+        respect_ignore_files,
+        ignore,
+    };
+    let base = base.map(|base| base.display().to_string()).unwrap_or_default();
+    let files = source.walk(&base, &options)?;
+    Ok(files
+        .into_iter()
+        .filter(|file| matcher.matches_path_with(Path::new(file), MATCH_OPTIONS))
+        .collect())
+}
+
+/// Strip redundant leading `./` segments so patterns and paths compare root-relative.
+pub fn normalize(path: &str) -> &str {
+    let mut path = path;
+    while let Some(rest) = path.strip_prefix("./") {
+        path = rest;
+    }
+    path
+}
+
+/// Split a glob pattern into the literal directory it is rooted at and the maximum walk depth.
+///
+/// Returns `None` as the base when the pattern starts with a wildcard (walk the current
+/// directory), and `None` as the depth when the pattern contains `**`.
+fn split_pattern(pattern: &str) -> (Option<PathBuf>, Option<usize>) {
+    let components: Vec<&str> = pattern.split('/').collect();
+    let literal = components
+        .iter()
+        .take_while(|c| !c.contains(['*', '?', '[']))
+        .count();
+
+    if literal == components.len() {
+        return (Some(PathBuf::from(pattern)), Some(0));
+    }
+
+    let base = match &components[..literal] {
+        [] => None,
+        [""] => Some(PathBuf::from("/")),
+        parts => Some(PathBuf::from(parts.join("/"))),
+    };
+    let rest = &components[literal..];
+    let depth = if rest.iter().any(|c| c.contains("**")) {
+        None
+    } else {
+        Some(rest.len())
+    };
+    (base, depth)
+}
+
+/// Build a gitignore-style matcher from `ignore_patterns` and the ignore file at the root
+/// of `source`.
+///
+/// The matcher expects root-relative paths.
+pub fn build_ignore(source: &dyn SkillSource, ignore_patterns: &[String]) -> Result<Gitignore, LintError> {
+    let mut builder = GitignoreBuilder::new(".");
+
+    if source.is_file(IGNORE_FILE) {
+        let content = source
+            .read(IGNORE_FILE)
+            .map_err(|e| LintError::FileRead(IGNORE_FILE.to_string(), e))?;
+        for line in content.lines() {
+            builder
+                .add_line(Some(PathBuf::from(IGNORE_FILE)), line)
+                .map_err(|e| LintError::IgnorePattern(IGNORE_FILE.to_string(), e.to_string()))?;
+        }
+    }
+
+    for pattern in ignore_patterns {
+        builder
+            .add_line(None, pattern)
+            .map_err(|e| LintError::IgnorePattern(pattern.clone(), e.to_string()))?;
+    }
+
+    builder
+        .build()
+        .map_err(|e| LintError::IgnorePattern(IGNORE_FILE.to_string(), e.to_string()))
+}
+
+/// Returns true if `path` (or one of its parent directories) is excluded by the matcher.
+fn is_ignored(ignore: &Gitignore, path: &Path) -> bool {
+    // Paths outside the root (absolute patterns) cannot be matched.
+    if path.has_root() {
+        return false;
+    }
+    ignore.matched_path_or_any_parents(path, false).is_ignore()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::source::{MemorySource, OsSource};
 
     #[test]
     fn test_no_matches_returns_empty() {
-        let result = discover_files(&["nonexistent_path_xyz/**/*.md".to_string()]).unwrap();
+        let result =
+            discover_files(&OsSource::new("."), &["nonexistent_path_xyz/**/*.md".to_string()], &[], true).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_split_pattern() {
+        assert_eq!(
+            split_pattern("./.github/**/SKILL.md"),
+            (Some(PathBuf::from("./.github")), None)
+        );
+        assert_eq!(split_pattern("skills/*/SKILL.md"), (Some(PathBuf::from("skills")), Some(2)));
+        assert_eq!(split_pattern("**/SKILL.md"), (None, None));
+        assert_eq!(split_pattern("*.md"), (None, Some(1)));
+        assert_eq!(split_pattern("docs/SKILL.md"), (Some(PathBuf::from("docs/SKILL.md")), Some(0)));
+        assert_eq!(split_pattern("/abs/*.md"), (Some(PathBuf::from("/abs")), Some(1)));
+    }
+
+    #[test]
+    fn test_discovers_repo_skills_relative_to_root() {
+        // The crate's tests run from the crate directory; the repo's own skills live one level up.
+        let result = discover_files(&OsSource::new(".."), &["./.github/**/SKILL.md".to_string()], &[], true).unwrap();
+        assert!(result.contains(&".github/skills/foo/SKILL.md".to_string()));
+        let mut sorted = result.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(result, sorted);
+    }
+
+    #[test]
+    fn test_single_star_does_not_cross_directories() {
+        let result = discover_files(&OsSource::new(".."), &[".github/*/SKILL.md".to_string()], &[], true).unwrap();
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn test_literal_pattern_relative_to_root() {
+        let result =
+            discover_files(&OsSource::new(".."), &["./.github/skills/bar/SKILL.md".to_string()], &[], true).unwrap();
+        assert_eq!(result, vec![".github/skills/bar/SKILL.md"]);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("./././a/b.md"), "a/b.md");
+        assert_eq!(normalize(".github/x"), ".github/x");
+    }
+
+    #[test]
+    fn test_ignore_patterns_match_parent_directories() {
+        let ignore = build_ignore(
+            &OsSource::new("nonexistent_root_xyz"),
+            &["node_modules/".to_string(), "fixtures/**".to_string()],
+        )
+        .unwrap();
+        assert!(is_ignored(&ignore, Path::new("node_modules/pkg/SKILL.md")));
+        assert!(is_ignored(&ignore, Path::new("./skills/node_modules/pkg/SKILL.md")));
+        assert!(is_ignored(&ignore, Path::new("fixtures/bad/SKILL.md")));
+        assert!(!is_ignored(&ignore, Path::new("skills/good/SKILL.md")));
+    }
+
+    #[test]
+    fn test_ignore_negation() {
+        let ignore = build_ignore(
+            &OsSource::new("nonexistent_root_xyz"),
+            &["vendor/*".to_string(), "!vendor/ours".to_string()],
+        )
+        .unwrap();
+        assert!(is_ignored(&ignore, Path::new("vendor/theirs/SKILL.md")));
+        assert!(!is_ignored(&ignore, Path::new("vendor/ours/SKILL.md")));
+    }
+
+    #[test]
+    fn test_discovers_from_memory_source() {
+        let source: MemorySource = [
+            (IGNORE_FILE, "skills/vendor/\n"),
+            ("skills/a/SKILL.md", ""),
+            ("skills/vendor/b/SKILL.md", ""),
+            ("skills/c/README.md", ""),
+            ("docs/SKILL.md", ""),
+        ]
+        .into_iter()
+        .collect();
+        let patterns = vec!["./skills/**/SKILL.md".to_string(), "docs/SKILL.md".to_string()];
+        let result = discover_files(&source, &patterns, &[], true).unwrap();
+        assert_eq!(result, vec!["docs/SKILL.md", "skills/a/SKILL.md"]);
+    }
 }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -6,7 +8,25 @@ pub enum LintError {
     ConfigRead(String, #[source] std::io::Error),
 
     #[error("failed to parse config file '{0}': {1}")]
-    ConfigParse(String, #[source] serde_json::Error),
+    ConfigParse(String, String),
+
+    #[error("invalid config:\n{}", format_issues(.0))]
+    ConfigInvalid(Vec<ConfigIssue>),
+
+    #[error("failed to resolve extends in '{0}': {1}")]
+    ConfigExtends(String, String),
+
+    #[error("invalid {0}: {1}")]
+    ConfigFlag(String, String),
+
+    #[error("no skills-lint config found in '{0}' or any parent directory")]
+    ConfigNotFound(String),
+
+    #[error("'{0}' has no \"{1}\" key")]
+    PackageJsonKey(String, &'static str),
+
+    #[error("no rules enabled: configure at least one rule under \"rules\"")]
+    NoRulesEnabled,
 
     #[error("unsupported model '{0}'. Supported models: {1:?}")]
     UnsupportedModel(String, Vec<&'static str>),
@@ -20,9 +40,59 @@ pub enum LintError {
     #[error("invalid glob pattern '{0}': {1}")]
     GlobPattern(String, String),
 
-    #[error("glob iteration error: {0}")]
-    GlobIteration(String),
+    #[error("invalid ignore pattern '{0}': {1}")]
+    IgnorePattern(String, String),
+
+    #[error("directory walk error: {0}")]
+    Walk(String),
 
     #[error("failed to read file '{0}': {1}")]
     FileRead(String, #[source] std::io::Error),
+
+    #[error("git error: {0}")]
+    Git(String),
+
+    #[error("failed to write cache '{0}': {1}")]
+    CacheWrite(String, #[source] std::io::Error),
+}
+
+/// A single problem found while validating a config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Config file the issue was found in. Empty until attached to a source.
+    pub file: String,
+    /// 1-based line and column of the offending key, if it could be located.
+    pub location: Option<(usize, usize)>,
+    /// Path of keys leading to the offending value, e.g. `["rules", "token-limit"]`.
+    pub path: Vec<String>,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn new(path: Vec<String>, message: impl Into<String>) -> Self {
+        Self {
+            file: String::new(),
+            location: None,
+            path,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(f, "{}:{line}:{column}: {}", self.file, self.message),
+            None if self.file.is_empty() => write!(f, "{}", self.message),
+            None => write!(f, "{}: {}", self.file, self.message),
+        }
+    }
+}
+
+fn format_issues(issues: &[ConfigIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| format!("  {issue}"))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
@@ -1,8 +1,11 @@
 pub mod cache;
 pub mod config;
+pub mod cost;
+pub mod diff;
 pub mod discovery;
 pub mod errors;
 pub mod lint;
 pub mod rules;
+pub mod source;
 pub mod tokenizer;
 pub mod types;
@@ -1,96 +1,385 @@
-use std::path::Path;
+use serde_json::json;
+use sha2::{Digest, Sha256};
 
-use crate::cache::TokenCache;
+use crate::cache::{Cache, TokenCache};
+use crate::config::effective::effective_rules;
 use crate::config::Config;
 use crate::discovery;
 use crate::errors::LintError;
-use crate::rules::{frontmatter_limit, skill_index_budget, skill_structure, token_limit, unique_fields};
-use crate::types::{LintFinding, LintReport, StructureFinding};
+use crate::rules::{frontmatter_limit, size_limit, skill_index_budget, skill_structure, token_limit, unique_fields};
+use crate::source::{OsSource, SkillSource};
+use crate::tokenizer;
+use crate::types::{FileResult, LintReport};
 
-/// Discover files based on config patterns.
-pub fn discover(config: &Config) -> Result<Vec<String>, LintError> {
-    discovery::discover_files(&config.patterns)
+/// Discover files in `source` based on config patterns, minus ignored paths.
+pub fn discover(config: &Config, source: &dyn SkillSource) -> Result<Vec<String>, LintError> {
+    discovery::discover_files(
+        source,
+        &config.patterns,
+        &config.ignore_patterns,
+        config.respect_ignore_files,
+    )
 }
 
-/// Lint a single file against all configured models. Returns token findings for that file.
-pub fn lint_file(config: &Config, file: &str, mut cache: Option<&mut TokenCache>) -> Result<Vec<LintFinding>, LintError> {
-    let content = std::fs::read_to_string(Path::new(file))
-        .map_err(|e| LintError::FileRead(file.to_string(), e))?;
+/// Read a root-relative file.
+pub fn read_file(source: &dyn SkillSource, file: &str) -> Result<String, LintError> {
+    source.read(file).map_err(|e| LintError::FileRead(file.to_string(), e))
+}
+
+/// Read every file once, in order.
+pub fn read_files(source: &dyn SkillSource, files: &[String]) -> Result<Vec<String>, LintError> {
+    files.iter().map(|file| read_file(source, file)).collect()
+}
+
+/// Rules checked per file, whose results are cached together.
+const PER_FILE_RULES: &[&str] = &["token-limit", "frontmatter-limit", size_limit::RULE, "skill-structure"];
 
-    let mut model_names: Vec<&String> = config.rules.token_limit.models.keys().collect();
-    model_names.sort();
+/// Key of the cached results for `file`: the hash of its content and of everything that
+/// decides its findings — the path, the effective per-file rules with their tokenizers,
+/// and the skills-lint version.
+fn result_key(config: &Config, file: &str, content: &str) -> String {
+    let rules: Vec<_> = effective_rules(config, file)
+        .into_iter()
+        .filter(|rule| PER_FILE_RULES.contains(&rule.rule))
+        .map(|rule| {
+            let fingerprint = rule.encoding.as_deref().map(tokenizer::fingerprint);
+            json!([rule.rule, rule.model, rule.encoding, fingerprint, rule.warning, rule.error])
+        })
+        .collect();
+    let settings = json!({ "version": env!("CARGO_PKG_VERSION"), "file": file, "rules": rules });
+    let content_hash = Sha256::digest(content.as_bytes());
+    let settings_hash = Sha256::digest(settings.to_string().as_bytes());
+    format!("{content_hash:x}:{settings_hash:x}")
+}
+
+/// Run the per-file rules on a file's content: token, size and frontmatter budgets, and
+/// `skill-structure`. With a cache, the complete result is reused while the content and
+/// the file's effective config are unchanged.
+pub fn lint_content(
+    config: &Config,
+    file: &str,
+    content: &str,
+    cache: Option<&mut (dyn Cache + '_)>,
+) -> Result<FileResult, LintError> {
+    let Some(cache) = cache else {
+        return check_content(config, file, content, None);
+    };
+    let key = result_key(config, file, content);
+    if let Some(result) = cache.get_result(&key) {
+        return Ok(result);
+    }
+    let result = check_content(config, file, content, Some(&mut *cache))?;
+    cache.insert_result(key, result.clone());
+    Ok(result)
+}
 
+fn check_content(
+    config: &Config,
+    file: &str,
+    content: &str,
+    mut cache: Option<&mut (dyn Cache + '_)>,
+) -> Result<FileResult, LintError> {
     let mut findings = Vec::new();
-    for model in &model_names {
-        if let Some(budget) = config.resolve_token_limit(file, model) {
-            let finding = token_limit::check("token-limit", file, model, &content, &budget, cache.as_deref_mut())?;
-            findings.push(finding);
+    if let Some(ref tl) = config.rules.token_limit {
+        let mut model_names: Vec<&String> = tl.models.keys().collect();
+        model_names.sort();
+
+        for model in &model_names {
+            if let Some(budget) = config.resolve_token_limit(file, model) {
+                let finding = token_limit::check("token-limit", file, model, content, &budget, cache.as_deref_mut())?;
+                findings.push(finding);
+            }
         }
     }
 
-    findings.extend(frontmatter_limit::check_file(config, file, cache)?);
+    findings.extend(size_limit::check_file(config, file, content));
+    findings.extend(frontmatter_limit::check_file(config, file, content, cache)?);
 
-    Ok(findings)
+    let structure = (config.rules.skill_structure == Some(true)).then(|| skill_structure::lint_file(file, content));
+
+    Ok(FileResult { findings, structure })
 }
 
-/// Check a single file for structural validity. Returns a finding if the rule is enabled.
-pub fn check_structure(config: &Config, file: &str) -> Result<Option<StructureFinding>, LintError> {
-    if config.rules.skill_structure != Some(true) {
-        return Ok(None);
-    }
+/// Read and lint a single file with the per-file rules.
+pub fn lint_file(
+    config: &Config,
+    source: &dyn SkillSource,
+    file: &str,
+    cache: Option<&mut (dyn Cache + '_)>,
+) -> Result<FileResult, LintError> {
+    let content = read_file(source, file)?;
+    lint_content(config, file, &content, cache)
+}
 
-    let content = std::fs::read_to_string(Path::new(file))
-        .map_err(|e| LintError::FileRead(file.to_string(), e))?;
+/// Callback told the index and path of each source before it is linted.
+type Progress<'a> = Box<dyn FnMut(usize, &str) + 'a>;
 
-    Ok(Some(skill_structure::lint_file(file, &content)))
+/// Lints skill sources held in memory against a config.
+///
+/// The linter never touches the filesystem: sources are given as `(path, content)` pairs
+/// with paths relative to the config root, and token counts and results are only cached
+/// in an injected [`Cache`].
+///
+/// ```
+/// use skills_lint_core::cache::MemoryCache;
+/// use skills_lint_core::config::Config;
+/// use skills_lint_core::lint::Linter;
+///
+/// let config = Config::from_value(serde_json::json!({
+///     "patterns": ["**/SKILL.md"],
+///     "rules": { "skill-structure": true, "unique-name": true }
+/// }))?;
+/// let mut cache = MemoryCache::default();
+/// let report = Linter::new(&config)
+///     .source("skills/a/SKILL.md", "---\nname: a\ndescription: First\n---\n\n# A\n")
+///     .source("skills/b/SKILL.md", "---\nname: a\ndescription: Second\n---\n\n# B\n")
+///     .cache(&mut cache)
+///     .run()?;
+/// assert_eq!(report.structure_findings.len(), 4);
+/// # Ok::<(), skills_lint_core::errors::LintError>(())
+/// ```
+pub struct Linter<'a> {
+    config: &'a Config,
+    files: Vec<String>,
+    contents: Vec<String>,
+    cache: Option<&'a mut dyn Cache>,
+    aggregate: bool,
+    progress: Option<Progress<'a>>,
 }
 
-/// Run the full lint pipeline using config-based file discovery.
-pub fn run(config: &Config) -> Result<LintReport, LintError> {
-    let files = discover(config)?;
-    let mut findings = Vec::new();
-    let mut structure_findings = Vec::new();
+impl<'a> Linter<'a> {
+    pub fn new(config: &'a Config) -> Self {
+        Self {
+            config,
+            files: Vec::new(),
+            contents: Vec::new(),
+            cache: None,
+            aggregate: true,
+            progress: None,
+        }
+    }
 
-    let mut cache = if config.cache {
-        Some(TokenCache::load())
-    } else {
-        None
-    };
+    /// Add a source file. Files are reported in the order they are added.
+    pub fn source(mut self, path: impl Into<String>, content: impl Into<String>) -> Self {
+        self.files.push(path.into());
+        self.contents.push(content.into());
+        self
+    }
+
+    /// Add several `(path, content)` sources.
+    pub fn sources<P, C>(self, sources: impl IntoIterator<Item = (P, C)>) -> Self
+    where
+        P: Into<String>,
+        C: Into<String>,
+    {
+        sources.into_iter().fold(self, |linter, (path, content)| linter.source(path, content))
+    }
+
+    /// Reuse token counts and per-file results from `cache`, and store new ones in it.
+    pub fn cache(mut self, cache: &'a mut dyn Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 
-    for file in &files {
-        findings.extend(lint_file(config, file, cache.as_mut())?);
-        if let Some(sf) = check_structure(config, file)? {
-            structure_findings.push(sf);
+    /// Whether to run the rules that look at all sources together: `skill-index-budget`,
+    /// `unique-name` and `unique-description`. On by default; turn it off when the
+    /// sources are only part of the skill set.
+    pub fn aggregate_rules(mut self, aggregate: bool) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// Call `progress` with the index and path of each source before it is linted, e.g. to
+    /// drive a progress bar.
+    pub fn on_file(mut self, progress: impl FnMut(usize, &str) + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Lint every source and return the findings.
+    pub fn run(mut self) -> Result<LintReport, LintError> {
+        let mut findings = Vec::new();
+        let mut structure_findings = Vec::new();
+
+        for (index, (file, content)) in self.files.iter().zip(&self.contents).enumerate() {
+            if let Some(ref mut progress) = self.progress {
+                progress(index, file);
+            }
+            let result = lint_content(self.config, file, content, self.cache.as_deref_mut())?;
+            findings.extend(result.findings);
+            structure_findings.extend(result.structure);
         }
+
+        if self.aggregate {
+            findings.extend(skill_index_budget::check_all(self.config, &self.contents, self.cache.as_deref_mut())?);
+            structure_findings.extend(unique_fields::check_all(self.config, &self.files, &self.contents));
+        }
+
+        Ok(LintReport::new(findings, structure_findings))
     }
+}
 
-    findings.extend(skill_index_budget::check_all(config, &files, cache.as_mut())?);
-    structure_findings.extend(unique_fields::check_all(config, &files)?);
+/// Run the full lint pipeline using config-based file discovery under the config root.
+pub fn run(config: &Config) -> Result<LintReport, LintError> {
+    run_source(config, &OsSource::new(&config.root))
+}
 
-    if let Some(ref c) = cache {
-        c.flush();
+/// Run the full lint pipeline on the files discovered in `source`.
+///
+/// Fails with [`LintError::CacheWrite`] if `cache` is enabled and the cache file cannot be
+/// written.
+pub fn run_source(config: &Config, source: &dyn SkillSource) -> Result<LintReport, LintError> {
+    let files = discover(config, source)?;
+    let contents = read_files(source, &files)?;
+    let mut cache = config.cache.then(|| TokenCache::load(&config.cache_dir()));
+
+    let mut linter = Linter::new(config).sources(files.into_iter().zip(contents));
+    if let Some(ref mut cache) = cache {
+        linter = linter.cache(cache);
     }
+    let report = linter.run()?;
 
-    Ok(LintReport::new(findings, structure_findings))
+    if let Some(ref c) = cache {
+        c.flush()?;
+    }
+    Ok(report)
 }
 
-/// Run the lint pipeline on a single file.
+/// Run the lint pipeline on a single file. Like [`run_source`], fails if the cache file
+/// cannot be written.
 pub fn run_single(config: &Config, file_path: &str) -> Result<LintReport, LintError> {
-    let mut cache = if config.cache {
-        Some(TokenCache::load())
-    } else {
-        None
-    };
+    let content = read_file(&OsSource::new(&config.root), file_path)?;
+    let mut cache = config.cache.then(|| TokenCache::load(&config.cache_dir()));
 
-    let findings = lint_file(config, file_path, cache.as_mut())?;
-    let mut structure_findings = Vec::new();
-    if let Some(sf) = check_structure(config, file_path)? {
-        structure_findings.push(sf);
+    let mut linter = Linter::new(config).source(file_path, content).aggregate_rules(false);
+    if let Some(ref mut cache) = cache {
+        linter = linter.cache(cache);
     }
+    let report = linter.run()?;
 
     if let Some(ref c) = cache {
-        c.flush();
+        c.flush()?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{MemoryCache, RunStats};
+    use crate::source::MemorySource;
+    use crate::types::Severity;
+    use std::path::Path;
+
+    fn config(error: usize) -> Config {
+        let json = format!(
+            r#"{{
+                "patterns": ["*.md"],
+                "rules": {{
+                    "token-limit": {{ "models": {{ "gpt-4o": {{ "warning": 2, "error": {error} }} }} }},
+                    "skill-structure": true
+                }}
+            }}"#
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_lint_content_reuses_cached_result() {
+        let mut cache = TokenCache::load(Path::new("nonexistent_dir_xyz"));
+        let content = "---\nname: a\ndescription: b\n---\n\nHello there, world.\n";
+
+        let first = lint_content(&config(100), "a.md", content, Some(&mut cache)).unwrap();
+        assert_eq!(first.findings.len(), 1);
+        assert!(first.structure.is_some());
+        let misses = cache.stats().last_run.misses;
+
+        let second = lint_content(&config(100), "a.md", content, Some(&mut cache)).unwrap();
+        assert_eq!(second.findings, first.findings);
+        assert_eq!(cache.stats().last_run.misses, misses);
+
+        // A different budget, path or content is a different key.
+        assert_ne!(result_key(&config(100), "a.md", content), result_key(&config(200), "a.md", content));
+        assert_ne!(result_key(&config(100), "a.md", content), result_key(&config(100), "b.md", content));
+        assert_ne!(result_key(&config(100), "a.md", content), result_key(&config(100), "a.md", "other"));
+        let changed = lint_content(&config(5), "a.md", content, Some(&mut cache)).unwrap();
+        assert_eq!(changed.findings[0].error_threshold, 5);
+    }
+
+    #[test]
+    fn test_cache_is_independent_of_checkout_location() {
+        let content = "---\nname: a\ndescription: b\n---\n\nHello.\n";
+        let base = std::env::temp_dir().join(format!("skills-lint-portable-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base);
+        let checkout = |name: &str| {
+            let root = base.join(name);
+            std::fs::create_dir_all(root.join("skills/a")).unwrap();
+            std::fs::write(root.join("skills/a/SKILL.md"), content).unwrap();
+            let mut config = config(100);
+            config.patterns = vec!["skills/**/SKILL.md".to_string()];
+            config.root = root;
+            config
+        };
+
+        // Lint one checkout, then move its cache file to a checkout at another path.
+        let one = checkout("one");
+        let first = run_source(&one, &OsSource::new(&one.root)).unwrap();
+        let file = std::fs::read_to_string(TokenCache::load(&one.cache_dir()).path()).unwrap();
+        assert!(!file.contains(&*one.root.to_string_lossy()));
+
+        let two = checkout("nested/two");
+        std::fs::create_dir_all(two.cache_dir()).unwrap();
+        std::fs::write(TokenCache::load(&two.cache_dir()).path(), file).unwrap();
+        let second = run_source(&two, &OsSource::new(&two.root)).unwrap();
+        assert_eq!(second.findings, first.findings);
+        let stats = TokenCache::load(&two.cache_dir()).stats();
+        assert_eq!(stats.last_run, RunStats { hits: 1, misses: 0 });
+
+        let _ = std::fs::remove_dir_all(&base);
     }
 
-    Ok(LintReport::new(findings, structure_findings))
+    #[test]
+    fn test_linter_lints_in_memory_sources() {
+        let config: Config = serde_json::from_str(
+            r#"{ "patterns": ["**/SKILL.md"], "rules": { "skill-structure": true, "unique-name": true } }"#,
+        )
+        .unwrap();
+        let sources = [
+            ("skills/a/SKILL.md", "---\nname: a\ndescription: First\n---\n\n# A\n"),
+            ("skills/b/SKILL.md", "---\nname: a\ndescription: Second\n---\n\n# B\n"),
+        ];
+        let mut cache = MemoryCache::default();
+
+        let report = Linter::new(&config).sources(sources).cache(&mut cache).run().unwrap();
+        let rules: Vec<&str> = report.structure_findings.iter().map(|f| f.rule.as_str()).collect();
+        assert_eq!(rules, vec!["skill-structure", "skill-structure", "unique-name", "unique-name"]);
+        assert_eq!(report.structure_findings[2].severity, Severity::Error);
+
+        let again = Linter::new(&config).sources(sources).cache(&mut cache).run().unwrap();
+        assert_eq!(again.structure_findings, report.structure_findings);
+
+        let partial = Linter::new(&config).sources(sources).aggregate_rules(false).run().unwrap();
+        assert!(partial.structure_findings.iter().all(|f| f.rule == "skill-structure"));
+
+        let mut seen = Vec::new();
+        Linter::new(&config).sources(sources).on_file(|index, file| seen.push((index, file.to_string()))).run().unwrap();
+        assert_eq!(seen, vec![(0, sources[0].0.to_string()), (1, sources[1].0.to_string())]);
+    }
+
+    #[test]
+    fn test_run_source_discovers_in_source() {
+        let mut config = config(100);
+        config.patterns = vec!["skills/**/SKILL.md".to_string()];
+        config.cache = false;
+        let source: MemorySource = [
+            ("skills/a/SKILL.md", "---\nname: a\ndescription: b\n---\n\nHello.\n"),
+            ("skills/a/notes.md", "Not a skill."),
+        ]
+        .into_iter()
+        .collect();
+
+        let report = run_source(&config, &source).unwrap();
+        assert_eq!(report.structure_findings.len(), 1);
+        assert_eq!(report.structure_findings[0].file, "skills/a/SKILL.md");
+    }
 }
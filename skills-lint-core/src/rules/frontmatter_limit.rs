@@ -1,6 +1,4 @@
-use std::path::Path;
-
-use crate::cache::TokenCache;
+use crate::cache::Cache;
 use crate::config::{Config, ResolvedBudget};
 use crate::errors::LintError;
 use crate::rules::skill_index_budget::extract_frontmatter;
@@ -13,7 +11,7 @@ pub fn check(
     model: &str,
     frontmatter: &str,
     budget: &ResolvedBudget,
-    cache: Option<&mut TokenCache>,
+    cache: Option<&mut (dyn Cache + '_)>,
 ) -> Result<LintFinding, LintError> {
     token_limit::check("frontmatter-limit", file, model, frontmatter, budget, cache)
 }
@@ -21,16 +19,18 @@ pub fn check(
 /// Check a single file's frontmatter against the frontmatter-limit rule for all configured models.
 ///
 /// Returns an empty vec if the rule is not configured or the file has no frontmatter.
-pub fn check_file(config: &Config, file: &str, mut cache: Option<&mut TokenCache>) -> Result<Vec<LintFinding>, LintError> {
+pub fn check_file(
+    config: &Config,
+    file: &str,
+    content: &str,
+    mut cache: Option<&mut (dyn Cache + '_)>,
+) -> Result<Vec<LintFinding>, LintError> {
     let fl = match config.rules.frontmatter_limit.as_ref() {
         Some(fl) => fl,
         None => return Ok(Vec::new()),
     };
 
-    let content = std::fs::read_to_string(Path::new(file))
-        .map_err(|e| LintError::FileRead(file.to_string(), e))?;
-
-    let frontmatter = match extract_frontmatter(&content) {
+    let frontmatter = match extract_frontmatter(content) {
         Some(fm) => fm,
         None => return Ok(Vec::new()),
     };
@@ -59,6 +59,7 @@ mod tests {
             encoding: "cl100k_base".to_string(),
             warning: 8000,
             error: 12000,
+            override_index: None,
         };
         let finding = check("test.md", "gpt-4", "name: tiny", &budget, None).unwrap();
         assert_eq!(finding.severity, Severity::Pass);
@@ -71,6 +72,7 @@ mod tests {
             encoding: "cl100k_base".to_string(),
             warning: 1,
             error: 2,
+            override_index: None,
         };
         let big = "word ".repeat(100);
         let finding = check("test.md", "gpt-4", &big, &budget, None).unwrap();
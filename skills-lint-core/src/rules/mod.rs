@@ -1,4 +1,5 @@
 pub mod frontmatter_limit;
+pub mod size_limit;
 pub mod skill_index_budget;
 pub mod skill_structure;
 pub mod token_limit;
@@ -0,0 +1,98 @@
+use crate::config::{Config, ResolvedSizeBudget, SizeMetric};
+use crate::types::{LintFinding, Severity};
+
+/// Rule name used in findings.
+pub const RULE: &str = "size-limit";
+
+/// Measure `content` in the unit of `metric`.
+pub fn measure(metric: SizeMetric, content: &str) -> usize {
+    match metric {
+        SizeMetric::Bytes => content.len(),
+        SizeMetric::Lines => content.lines().count(),
+        SizeMetric::Words => content.split_whitespace().count(),
+    }
+}
+
+/// Check one metric of a file against a resolved budget.
+///
+/// The finding's `model` holds the metric label and `token_count` the measured size, so
+/// size findings flow through the same report as token findings.
+pub fn check(file: &str, metric: SizeMetric, content: &str, budget: &ResolvedSizeBudget) -> LintFinding {
+    let size = measure(metric, content);
+
+    let severity = if size >= budget.error {
+        Severity::Error
+    } else if size >= budget.warning {
+        Severity::Warning
+    } else {
+        Severity::Pass
+    };
+
+    LintFinding {
+        rule: RULE.to_string(),
+        file: file.to_string(),
+        model: metric.label().to_string(),
+        token_count: size,
+        warning_threshold: budget.warning,
+        error_threshold: budget.error,
+        severity,
+    }
+}
+
+/// Check a file against every configured size-limit metric.
+///
+/// Returns an empty vec if the rule is not configured.
+pub fn check_file(config: &Config, file: &str, content: &str) -> Vec<LintFinding> {
+    SizeMetric::ALL
+        .into_iter()
+        .filter_map(|metric| {
+            config
+                .resolve_size_limit(file, metric)
+                .map(|budget| check(file, metric, content, &budget))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let content = "---\nname: a\n---\n\nSome   words here.\n";
+        assert_eq!(measure(SizeMetric::Bytes, content), content.len());
+        assert_eq!(measure(SizeMetric::Lines, content), 5);
+        assert_eq!(measure(SizeMetric::Words, content), 7);
+    }
+
+    #[test]
+    fn test_check_file_with_override() {
+        let json = r#"{
+            "patterns": ["*.md"],
+            "rules": {
+                "size-limit": {
+                    "maxLines": { "warning": 2, "error": 4 },
+                    "maxWords": { "warning": 100, "error": 200 }
+                }
+            },
+            "overrides": [{
+                "files": ["long.md"],
+                "rules": { "size-limit": { "maxLines": { "error": 10 } } }
+            }]
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let content = "a\nb\nc\n";
+
+        let findings = check_file(&config, "short.md", content);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].model, "lines");
+        assert_eq!(findings[0].token_count, 3);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(findings[1].model, "words");
+        assert_eq!(findings[1].severity, Severity::Pass);
+
+        let findings = check_file(&config, "long.md", "a\n".repeat(5).as_str());
+        assert_eq!(findings[0].error_threshold, 10);
+        assert_eq!(findings[0].severity, Severity::Warning);
+    }
+}
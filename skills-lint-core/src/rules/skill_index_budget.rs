@@ -1,6 +1,4 @@
-use std::path::Path;
-
-use crate::cache::TokenCache;
+use crate::cache::Cache;
 use crate::config::{Config, ResolvedBudget};
 use crate::errors::LintError;
 use crate::rules::token_limit;
@@ -35,34 +33,31 @@ pub fn extract_frontmatter(content: &str) -> Option<String> {
     None
 }
 
+/// Join the frontmatter of all files into the skill index loaded at startup.
+pub fn aggregate_frontmatter(contents: &[String]) -> String {
+    let frontmatter_parts: Vec<String> = contents.iter().filter_map(|content| extract_frontmatter(content)).collect();
+    frontmatter_parts.join("\n")
+}
+
 /// Check the aggregated frontmatter string against a resolved budget for one model.
 pub fn check(
     aggregated: &str,
     model: &str,
     budget: &ResolvedBudget,
-    cache: Option<&mut TokenCache>,
+    cache: Option<&mut (dyn Cache + '_)>,
 ) -> Result<LintFinding, LintError> {
     token_limit::check("skill-index-budget", AGGREGATE_LABEL, model, aggregated, budget, cache)
 }
 
-/// Check all discovered files' frontmatter against the skill-index-budget rule.
+/// Check the frontmatter of all discovered files' contents against the skill-index-budget rule.
 ///
 /// Returns an empty vec if the rule is not configured.
-pub fn check_all(config: &Config, files: &[String], mut cache: Option<&mut TokenCache>) -> Result<Vec<LintFinding>, LintError> {
+pub fn check_all(config: &Config, contents: &[String], mut cache: Option<&mut (dyn Cache + '_)>) -> Result<Vec<LintFinding>, LintError> {
     if config.rules.skill_index_budget.is_none() {
         return Ok(Vec::new());
     }
 
-    let mut frontmatter_parts = Vec::new();
-    for file in files {
-        let content = std::fs::read_to_string(Path::new(file))
-            .map_err(|e| LintError::FileRead(file.clone(), e))?;
-        if let Some(fm) = extract_frontmatter(&content) {
-            frontmatter_parts.push(fm);
-        }
-    }
-
-    let aggregated = frontmatter_parts.join("\n");
+    let aggregated = aggregate_frontmatter(contents);
 
     let sib = config.rules.skill_index_budget.as_ref().unwrap();
     let mut model_names: Vec<&String> = sib.models.keys().collect();
@@ -122,6 +117,7 @@ mod tests {
             encoding: "cl100k_base".to_string(),
             warning: 8000,
             error: 12000,
+            override_index: None,
         };
         let finding = check("name: tiny", "gpt-4", &budget, None).unwrap();
         assert_eq!(finding.severity, Severity::Pass);
@@ -134,6 +130,7 @@ mod tests {
             encoding: "cl100k_base".to_string(),
             warning: 1,
             error: 2,
+            override_index: None,
         };
         // Generate enough text to exceed 2 tokens
         let big = "word ".repeat(100);
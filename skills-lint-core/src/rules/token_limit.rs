@@ -1,4 +1,4 @@
-use crate::cache::TokenCache;
+use crate::cache::Cache;
 use crate::config::ResolvedBudget;
 use crate::tokenizer;
 use crate::types::{LintFinding, Severity};
@@ -11,7 +11,7 @@ pub fn check(
     model: &str,
     content: &str,
     budget: &ResolvedBudget,
-    cache: Option<&mut TokenCache>,
+    cache: Option<&mut (dyn Cache + '_)>,
 ) -> Result<LintFinding, LintError> {
     let token_count = match cache {
         Some(c) => c.count_tokens(content, &budget.encoding)?,
@@ -47,6 +47,7 @@ mod tests {
             encoding: "cl100k_base".to_string(),
             warning: 8000,
             error: 12000,
+            override_index: None,
         };
         let finding = check("token-limit", "test.md", "gpt-4", "Hello", &budget, None).unwrap();
         assert_eq!(finding.severity, Severity::Pass);
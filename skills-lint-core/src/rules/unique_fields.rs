@@ -1,8 +1,6 @@
 use std::collections::HashMap;
-use std::path::Path;
 
 use crate::config::Config;
-use crate::errors::LintError;
 use crate::rules::skill_index_budget::extract_frontmatter;
 use crate::types::{Severity, StructureFinding};
 
@@ -20,26 +18,24 @@ fn extract_field<'a>(frontmatter: &'a str, field: &str) -> Option<&'a str> {
     None
 }
 
-/// Check all discovered files for duplicate names and descriptions.
+/// Check all discovered files for duplicate names and descriptions. `contents` holds the
+/// content of each file in `files`.
 ///
 /// Returns an empty vec if neither rule is configured.
-pub fn check_all(config: &Config, files: &[String]) -> Result<Vec<StructureFinding>, LintError> {
+pub fn check_all(config: &Config, files: &[String], contents: &[String]) -> Vec<StructureFinding> {
     let check_name = config.rules.unique_name == Some(true);
     let check_desc = config.rules.unique_description == Some(true);
 
     if !check_name && !check_desc {
-        return Ok(Vec::new());
+        return Vec::new();
     }
 
     // Map of field value -> list of file paths that have that value.
     let mut names: HashMap<String, Vec<String>> = HashMap::new();
     let mut descriptions: HashMap<String, Vec<String>> = HashMap::new();
 
-    for file in files {
-        let content = std::fs::read_to_string(Path::new(file))
-            .map_err(|e| LintError::FileRead(file.clone(), e))?;
-
-        let fm = match extract_frontmatter(&content) {
+    for (file, content) in files.iter().zip(contents) {
+        let fm = match extract_frontmatter(content) {
             Some(fm) => fm,
             None => continue,
         };
@@ -87,11 +83,8 @@ pub fn check_all(config: &Config, files: &[String]) -> Result<Vec<StructureFindi
     let mut findings = Vec::new();
 
     // Produce a finding per file, in file order.
-    for file in files {
-        let content = std::fs::read_to_string(Path::new(file))
-            .map_err(|e| LintError::FileRead(file.clone(), e))?;
-
-        let fm = match extract_frontmatter(&content) {
+    for (file, content) in files.iter().zip(contents) {
+        let fm = match extract_frontmatter(content) {
             Some(fm) => fm,
             None => continue,
         };
@@ -160,7 +153,7 @@ pub fn check_all(config: &Config, files: &[String]) -> Result<Vec<StructureFindi
         }
     }
 
-    Ok(findings)
+    findings
 }
 
 #[cfg(test)]
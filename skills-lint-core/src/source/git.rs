@@ -0,0 +1,349 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::Mutex;
+
+use ignore::gitignore::Gitignore;
+
+use super::{depth, split_path, walk_tree, IgnoreRules, OuterIgnores, SkillSource, WalkOptions, IGNORE_FILES};
+use crate::discovery::normalize;
+use crate::errors::LintError;
+
+/// Mode of a symlink in a git tree. Symlinks are not followed.
+const SYMLINK_MODE: &str = "120000";
+
+/// Files of a commit in a local git repository, read from the object database without
+/// checking the commit out. Requires the `git` executable.
+///
+/// Walks honour the ignore files a walk on disk inside the repository does: `.ignore` and
+/// `.gitignore` files of the commit, including those in parent directories of `dir`, the
+/// repository's `info/exclude` and the global excludes file.
+#[derive(Debug)]
+pub struct GitSource {
+    /// Directory git runs in.
+    dir: PathBuf,
+    commit: String,
+    /// Path of `dir` relative to the repository root, ending in `/` unless empty.
+    prefix: String,
+    /// Blob id of every file, keyed by its path relative to `dir`.
+    blobs: BTreeMap<String, String>,
+    /// Blob ids of the ignore files in parent directories of `dir` within the repository,
+    /// keyed by their path relative to the repository root.
+    parent_ignores: BTreeMap<String, String>,
+    /// The repository's `info/exclude` file.
+    exclude: PathBuf,
+    /// `git cat-file --batch` process serving all reads, started on the first one.
+    cat_file: Mutex<Option<CatFile>>,
+}
+
+impl GitSource {
+    /// Open the tree of `rev` as seen from `dir`, a directory in a git work tree.
+    ///
+    /// Paths are relative to `dir`, so the source lines up with a config root in the work
+    /// tree; files outside `dir` are not part of it.
+    pub fn open(dir: &Path, rev: &str) -> Result<Self, LintError> {
+        if rev.starts_with('-') {
+            return Err(LintError::Git(format!("invalid revision '{rev}'")));
+        }
+        let paths = git(dir, &["rev-parse", "--show-prefix", "--git-path", "info/exclude"])?;
+        let paths = String::from_utf8_lossy(&paths);
+        let mut lines = paths.lines();
+        let prefix = lines.next().unwrap_or_default().to_string();
+        let exclude = dir.join(lines.next().unwrap_or_default());
+        let commit = git_text(dir, &["rev-parse", "--verify", "--quiet", &format!("{rev}^{{commit}}")])
+            .map_err(|_| LintError::Git(format!("unknown revision '{rev}'")))?;
+
+        let listing = git(dir, &["ls-tree", "-r", "-z", "--full-tree", &commit])?;
+        let mut blobs = BTreeMap::new();
+        let mut parent_ignores = BTreeMap::new();
+        for record in listing.split(|&b| b == 0).filter(|r| !r.is_empty()) {
+            let record = String::from_utf8_lossy(record);
+            let Some((meta, path)) = record.split_once('\t') else {
+                continue;
+            };
+            let mut meta = meta.split(' ');
+            let (Some(mode), Some("blob"), Some(id)) = (meta.next(), meta.next(), meta.next()) else {
+                continue;
+            };
+            if mode == SYMLINK_MODE {
+                continue;
+            }
+            if let Some(relative) = path.strip_prefix(prefix.as_str()) {
+                blobs.insert(relative.to_string(), id.to_string());
+            } else if is_parent_ignore_file(path, &prefix) {
+                parent_ignores.insert(path.to_string(), id.to_string());
+            }
+        }
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            commit,
+            prefix,
+            blobs,
+            parent_ignores,
+            exclude,
+            cat_file: Mutex::new(None),
+        })
+    }
+
+    /// Full id of the commit the source reads from.
+    pub fn commit(&self) -> &str {
+        &self.commit
+    }
+
+    /// Read the blob `id` through the shared `git cat-file --batch` process.
+    fn read_blob(&self, id: &str) -> io::Result<Vec<u8>> {
+        let mut cat_file = self.cat_file.lock().unwrap_or_else(|e| e.into_inner());
+        if cat_file.is_none() {
+            *cat_file = Some(CatFile::start(&self.dir)?);
+        }
+        let result = cat_file.as_mut().expect("cat-file process was started").read(id);
+        if result.is_err() {
+            // Restart the process on the next read rather than reuse a broken stream.
+            *cat_file = None;
+        }
+        result
+    }
+
+    /// Ignore rules from outside `dir`: ignore files of parent directories in the commit,
+    /// then `info/exclude` and the global excludes file.
+    fn outer_ignores(&self) -> Result<OuterIgnores, LintError> {
+        let mut parents = Vec::new();
+        for (path, id) in &self.parent_ignores {
+            let (dir, name) = split_path(path);
+            let Some(name) = IGNORE_FILES.iter().find(|n| **n == name) else {
+                continue;
+            };
+            let below = self.prefix.strip_prefix(dir).unwrap_or(&self.prefix).trim_start_matches('/');
+            let content = self.read_blob(id).map(|c| String::from_utf8_lossy(&c).into_owned()).unwrap_or_default();
+            parents.push((depth(dir), *name, IgnoreRules::parse("", below, path, &content)?));
+        }
+        parents.sort_by_key(|(depth, _, _)| std::cmp::Reverse(*depth));
+        let parents = parents.into_iter().map(|(_, name, rules)| (name, rules)).collect();
+
+        let mut excludes = Vec::new();
+        if let Ok(content) = std::fs::read_to_string(&self.exclude) {
+            let display = self.exclude.display().to_string();
+            excludes.push(IgnoreRules::parse("", &self.prefix, &display, &content)?);
+        }
+        let (global, _) = Gitignore::global();
+        excludes.push(IgnoreRules {
+            dir: String::new(),
+            prefix: self.prefix.clone(),
+            matcher: global,
+        });
+        Ok(OuterIgnores { parents, excludes })
+    }
+}
+
+impl Clone for GitSource {
+    fn clone(&self) -> Self {
+        Self {
+            dir: self.dir.clone(),
+            commit: self.commit.clone(),
+            prefix: self.prefix.clone(),
+            blobs: self.blobs.clone(),
+            parent_ignores: self.parent_ignores.clone(),
+            exclude: self.exclude.clone(),
+            cat_file: Mutex::new(None),
+        }
+    }
+}
+
+impl SkillSource for GitSource {
+    fn read(&self, path: &str) -> io::Result<String> {
+        let id = self.blobs.get(normalize(path)).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("no such file in commit {}", &self.commit[..12]))
+        })?;
+        let content = self.read_blob(id)?;
+        String::from_utf8(content).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "file is not valid UTF-8"))
+    }
+
+    fn is_file(&self, path: &str) -> bool {
+        self.blobs.contains_key(normalize(path))
+    }
+
+    fn walk(&self, dir: &str, options: &WalkOptions) -> Result<Vec<String>, LintError> {
+        let outer = if options.respect_ignore_files {
+            self.outer_ignores()?
+        } else {
+            OuterIgnores::default()
+        };
+        walk_tree(self.blobs.keys().map(String::as_str), normalize(dir), options, &outer, |path| {
+            self.read(path).ok()
+        })
+    }
+}
+
+/// Returns true if the repository path `path` is an ignore file in a directory above the
+/// directory `prefix`.
+fn is_parent_ignore_file(path: &str, prefix: &str) -> bool {
+    let (dir, name) = split_path(path);
+    IGNORE_FILES.contains(&name) && (dir.is_empty() || prefix.starts_with(&format!("{dir}/")))
+}
+
+/// A running `git cat-file --batch` process, which prints the blobs whose ids are written
+/// to it.
+#[derive(Debug)]
+struct CatFile {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl CatFile {
+    fn start(dir: &Path) -> io::Result<Self> {
+        let mut child = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["cat-file", "--batch"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take().map(BufReader::new);
+        match stdout {
+            Some(stdout) => Ok(Self { child, stdin, stdout }),
+            None => Err(io::Error::other("git cat-file has no output")),
+        }
+    }
+
+    /// Read the content of the blob `id`.
+    fn read(&mut self, id: &str) -> io::Result<Vec<u8>> {
+        let stdin = self.stdin.as_mut().ok_or_else(|| io::Error::other("git cat-file is closed"))?;
+        writeln!(stdin, "{id}")?;
+        stdin.flush()?;
+
+        // Each object is printed as "<id> <type> <size>\n<content>\n".
+        let mut header = String::new();
+        self.stdout.read_line(&mut header)?;
+        let mut fields = header.split_whitespace();
+        let size = match (fields.next(), fields.next(), fields.next()) {
+            (Some(_), Some("blob"), Some(size)) => size.parse::<usize>().map_err(io::Error::other)?,
+            _ => return Err(io::Error::other(format!("git cat-file: {}", header.trim()))),
+        };
+        let mut content = vec![0; size + 1];
+        self.stdout.read_exact(&mut content)?;
+        content.pop();
+        Ok(content)
+    }
+}
+
+impl Drop for CatFile {
+    fn drop(&mut self) {
+        // Closing stdin ends the batch, so the process exits.
+        drop(self.stdin.take());
+        let _ = self.child.wait();
+    }
+}
+
+/// Run git in `dir` and return its standard output.
+fn git(dir: &Path, args: &[&str]) -> Result<Vec<u8>, LintError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| LintError::Git(format!("failed to run git: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(LintError::Git(format!("git {} failed: {}", args[0], stderr.trim())));
+    }
+    Ok(output.stdout)
+}
+
+/// Run git in `dir` and return its output as a single trimmed line.
+fn git_text(dir: &Path, args: &[&str]) -> Result<String, LintError> {
+    let output = git(dir, args)?;
+    Ok(String::from_utf8_lossy(&output).trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::OsSource;
+    use ignore::gitignore::Gitignore;
+
+    /// Create a repository with one commit in a fresh temporary directory.
+    fn repo(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skills-lint-git-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for (path, content) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let run = |args: &[&str]| git(&dir, args).unwrap();
+        run(&["init", "-q"]);
+        // Force-add, so files matched by ignore files are committed too.
+        run(&["add", "-A", "-f"]);
+        run(&["-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "-q", "-m", "init"]);
+        dir
+    }
+
+    #[test]
+    fn test_reads_committed_tree_not_work_tree() {
+        let dir = repo("tree", &[("sub/skills/a/SKILL.md", "committed"), ("top.md", "top")]);
+        std::fs::write(dir.join("sub/skills/a/SKILL.md"), "changed").unwrap();
+        std::fs::write(dir.join("sub/skills/b.md"), "untracked").unwrap();
+
+        let source = GitSource::open(&dir.join("sub"), "HEAD").unwrap();
+        assert_eq!(source.commit().len(), 40);
+        assert_eq!(source.read("./skills/a/SKILL.md").unwrap(), "committed");
+        assert!(!source.is_file("skills/b.md"));
+        assert!(!source.is_file("top.md"));
+
+        let ignore = Gitignore::empty();
+        let options = WalkOptions {
+            max_depth: None,
+            respect_ignore_files: true,
+            ignore: &ignore,
+        };
+        assert_eq!(source.walk("skills", &options).unwrap(), vec!["skills/a/SKILL.md"]);
+
+        assert!(matches!(GitSource::open(&dir, "no-such-branch"), Err(LintError::Git(_))));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_walk_matches_walk_on_disk() {
+        let dir = repo(
+            "ignores",
+            &[
+                (".gitignore", "vendor/\n"),
+                ("sub/.ignore", "scratch/\n"),
+                ("sub/skills/a/SKILL.md", "a"),
+                ("sub/skills/vendor/b/SKILL.md", "b"),
+                ("sub/skills/drafts/c/SKILL.md", "c"),
+                ("sub/skills/scratch/d/SKILL.md", "d"),
+                ("sub/skills/.hidden/e/SKILL.md", "e"),
+            ],
+        );
+        std::fs::write(dir.join(".git/info/exclude"), "drafts/\n").unwrap();
+
+        let ignore = Gitignore::empty();
+        let options = WalkOptions {
+            max_depth: None,
+            respect_ignore_files: true,
+            ignore: &ignore,
+        };
+        let sorted = |mut files: Vec<String>| {
+            files.sort();
+            files
+        };
+        let root = dir.join("sub");
+        let on_disk = sorted(OsSource::new(&root).walk("skills", &options).unwrap());
+        let in_git = sorted(GitSource::open(&root, "HEAD").unwrap().walk("skills", &options).unwrap());
+        assert_eq!(on_disk, vec!["skills/a/SKILL.md"]);
+        assert_eq!(in_git, on_disk);
+
+        let options = WalkOptions {
+            respect_ignore_files: false,
+            ..options
+        };
+        assert_eq!(GitSource::open(&root, "HEAD").unwrap().walk("skills", &options).unwrap().len(), 5);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
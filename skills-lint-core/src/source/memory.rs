@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+use std::io;
+
+use super::{walk_tree, OuterIgnores, SkillSource, WalkOptions};
+use crate::discovery::normalize;
+use crate::errors::LintError;
+
+/// Files held in memory, e.g. for tests or for skills stored outside the filesystem.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    files: BTreeMap<String, String>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace the file at the root-relative `path`.
+    pub fn insert(&mut self, path: impl Into<String>, content: impl Into<String>) {
+        let path = path.into();
+        self.files.insert(normalize(&path).to_string(), content.into());
+    }
+}
+
+impl<P: Into<String>, C: Into<String>> FromIterator<(P, C)> for MemorySource {
+    fn from_iter<I: IntoIterator<Item = (P, C)>>(iter: I) -> Self {
+        let mut source = Self::new();
+        for (path, content) in iter {
+            source.insert(path, content);
+        }
+        source
+    }
+}
+
+impl SkillSource for MemorySource {
+    fn read(&self, path: &str) -> io::Result<String> {
+        self.files
+            .get(normalize(path))
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such file in memory"))
+    }
+
+    fn is_file(&self, path: &str) -> bool {
+        self.files.contains_key(normalize(path))
+    }
+
+    fn walk(&self, dir: &str, options: &WalkOptions) -> Result<Vec<String>, LintError> {
+        let outer = OuterIgnores::default();
+        walk_tree(self.files.keys().map(String::as_str), normalize(dir), options, &outer, |path| {
+            self.files.get(path).cloned()
+        })
+    }
+}
@@ -0,0 +1,240 @@
+use std::io;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+
+use crate::errors::LintError;
+
+pub mod git;
+pub mod memory;
+pub mod os;
+
+pub use git::GitSource;
+pub use memory::MemorySource;
+pub use os::OsSource;
+
+/// Ignore files honoured by a walk that respects ignore files. Like on disk, `.ignore`
+/// rules take precedence over `.gitignore` rules at any depth.
+const IGNORE_FILES: &[&str] = &[".ignore", ".gitignore"];
+
+/// A tree of files that skills are discovered in and read from: a directory on disk, files
+/// held in memory, or a tree in a git repository.
+///
+/// Paths are relative to the root of the source and separated by `/`.
+pub trait SkillSource {
+    /// Read a file as UTF-8 text.
+    fn read(&self, path: &str) -> io::Result<String>;
+
+    /// Whether `path` names a file.
+    fn is_file(&self, path: &str) -> bool;
+
+    /// List the files below the directory `dir`, `""` being the root, that `options` does
+    /// not exclude.
+    fn walk(&self, dir: &str, options: &WalkOptions) -> Result<Vec<String>, LintError>;
+}
+
+/// Which files a [`SkillSource::walk`] returns.
+pub struct WalkOptions<'a> {
+    /// Levels below the walked directory to descend, where its own files are at depth 1.
+    /// `None` walks the whole tree.
+    pub max_depth: Option<usize>,
+    /// Skip hidden entries and anything excluded by `.gitignore` or `.ignore` files.
+    pub respect_ignore_files: bool,
+    /// Matcher for `ignorePatterns` and `.skills-lintignore`, taking root-relative paths.
+    pub ignore: &'a Gitignore,
+}
+
+/// Rules of one ignore file. A root-relative path below `dir` is matched as `prefix`
+/// followed by its path relative to `dir`.
+struct IgnoreRules {
+    dir: String,
+    prefix: String,
+    matcher: Gitignore,
+}
+
+impl IgnoreRules {
+    /// Build the rules of the ignore file `path` from its content.
+    fn parse(dir: &str, prefix: &str, path: &str, content: &str) -> Result<Self, LintError> {
+        let mut builder = GitignoreBuilder::new(dir);
+        for line in content.lines() {
+            builder
+                .add_line(None, line)
+                .map_err(|e| LintError::IgnorePattern(path.to_string(), e.to_string()))?;
+        }
+        let matcher = builder
+            .build()
+            .map_err(|e| LintError::IgnorePattern(path.to_string(), e.to_string()))?;
+        Ok(Self {
+            dir: dir.to_string(),
+            prefix: prefix.to_string(),
+            matcher,
+        })
+    }
+
+    fn matched(&self, path: &str) -> Match<()> {
+        let Some(relative) = strip_dir(path, &self.dir) else {
+            return Match::None;
+        };
+        match self.matcher.matched_path_or_any_parents(format!("{}{relative}", self.prefix), false) {
+            Match::Ignore(_) => Match::Ignore(()),
+            Match::Whitelist(_) => Match::Whitelist(()),
+            Match::None => Match::None,
+        }
+    }
+}
+
+/// Ignore rules from outside a tree that still apply to it, such as a git tree below the
+/// root of its repository.
+#[derive(Default)]
+struct OuterIgnores {
+    /// Ignore files of parent directories, with their file name, deepest first.
+    parents: Vec<(&'static str, IgnoreRules)>,
+    /// The repository's `info/exclude` and the global excludes file, in that order.
+    excludes: Vec<IgnoreRules>,
+}
+
+/// Walk a source whose file paths are all known up front, with the same filters as a walk
+/// on disk. `read` returns the content of a file, and is used for ignore files.
+fn walk_tree<'p>(
+    paths: impl Iterator<Item = &'p str> + Clone,
+    dir: &str,
+    options: &WalkOptions,
+    outer: &OuterIgnores,
+    read: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<String>, LintError> {
+    let dir = dir.trim_end_matches('/');
+    let ignore_files = if options.respect_ignore_files {
+        ignore_file_matchers(paths.clone(), read)?
+    } else {
+        Vec::new()
+    };
+    let mut rules: Vec<&IgnoreRules> = Vec::new();
+    for name in IGNORE_FILES {
+        rules.extend(ignore_files.iter().filter(|(n, _)| n == name).map(|(_, r)| r));
+        rules.extend(outer.parents.iter().filter(|(n, _)| n == name).map(|(_, r)| r));
+    }
+    rules.extend(&outer.excludes);
+
+    let mut files = Vec::new();
+    for path in paths {
+        let Some(relative) = strip_dir(path, dir) else {
+            continue;
+        };
+        let components: Vec<&str> = relative.split('/').collect();
+        if options.max_depth.is_some_and(|max| components.len() > max) {
+            continue;
+        }
+        if options.respect_ignore_files
+            && (components.iter().any(|c| c.starts_with('.')) || is_ignored_by_files(&rules, path))
+        {
+            continue;
+        }
+        if options.ignore.matched_path_or_any_parents(path, false).is_ignore() {
+            continue;
+        }
+        files.push(path.to_string());
+    }
+    Ok(files)
+}
+
+/// Return `path` relative to `dir`, or None if it is not below it.
+fn strip_dir<'p>(path: &'p str, dir: &str) -> Option<&'p str> {
+    if dir.is_empty() {
+        return Some(path);
+    }
+    path.strip_prefix(dir)?.strip_prefix('/')
+}
+
+/// Directory of `path` and its file name, `""` being the root.
+fn split_path(path: &str) -> (&str, &str) {
+    path.rsplit_once('/').unwrap_or(("", path))
+}
+
+/// Number of directories `dir` lies below the root.
+fn depth(dir: &str) -> usize {
+    if dir.is_empty() {
+        0
+    } else {
+        dir.split('/').count()
+    }
+}
+
+/// Build the rules of every ignore file in the tree, with its file name, deepest first.
+fn ignore_file_matchers<'p>(
+    paths: impl Iterator<Item = &'p str>,
+    read: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<(&'static str, IgnoreRules)>, LintError> {
+    let mut matchers = Vec::new();
+    for path in paths {
+        let (dir, name) = split_path(path);
+        let Some(name) = IGNORE_FILES.iter().find(|n| **n == name) else {
+            continue;
+        };
+        let content = read(path).unwrap_or_default();
+        matchers.push((*name, IgnoreRules::parse(dir, "", path, &content)?));
+    }
+    matchers.sort_by_key(|(_, rules)| std::cmp::Reverse(depth(&rules.dir)));
+    Ok(matchers)
+}
+
+/// Returns true if the first rules that decide on `path` exclude it.
+fn is_ignored_by_files(rules: &[&IgnoreRules], path: &str) -> bool {
+    for rules in rules {
+        match rules.matched(path) {
+            Match::Ignore(()) => return true,
+            Match::Whitelist(()) => return false,
+            Match::None => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk(source: &MemorySource, dir: &str, max_depth: Option<usize>, respect_ignore_files: bool) -> Vec<String> {
+        let ignore = Gitignore::empty();
+        let options = WalkOptions {
+            max_depth,
+            respect_ignore_files,
+            ignore: &ignore,
+        };
+        source.walk(dir, &options).unwrap()
+    }
+
+    #[test]
+    fn test_walk_tree_limits_depth_to_directory() {
+        let source: MemorySource = [
+            ("skills/a/SKILL.md", ""),
+            ("skills/a/deep/SKILL.md", ""),
+            ("skills-other/SKILL.md", ""),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(walk(&source, "skills", Some(2), true), vec!["skills/a/SKILL.md"]);
+        assert_eq!(walk(&source, "skills", None, true).len(), 2);
+        assert_eq!(walk(&source, "", None, true).len(), 3);
+    }
+
+    #[test]
+    fn test_walk_tree_respects_nested_ignore_files() {
+        let source: MemorySource = [
+            (".gitignore", "vendor/\n"),
+            ("skills/.ignore", "draft-*\n!draft-keep/\n"),
+            ("skills/.gitignore", "!draft-x/\n"),
+            ("skills/a/SKILL.md", ""),
+            ("skills/draft-x/SKILL.md", ""),
+            ("skills/draft-keep/SKILL.md", ""),
+            ("vendor/b/SKILL.md", ""),
+            (".hidden/c/SKILL.md", ""),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            walk(&source, "", None, true),
+            vec!["skills/a/SKILL.md", "skills/draft-keep/SKILL.md"]
+        );
+        assert_eq!(walk(&source, "", None, false).len(), 8);
+    }
+}
@@ -0,0 +1,75 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::Gitignore;
+use ignore::WalkBuilder;
+
+use super::{SkillSource, WalkOptions};
+use crate::errors::LintError;
+
+/// Files in a directory on disk.
+#[derive(Debug, Clone)]
+pub struct OsSource {
+    root: PathBuf,
+}
+
+impl OsSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl SkillSource for OsSource {
+    fn read(&self, path: &str) -> io::Result<String> {
+        std::fs::read_to_string(self.root.join(path))
+    }
+
+    fn is_file(&self, path: &str) -> bool {
+        self.root.join(path).is_file()
+    }
+
+    /// Walk `dir` on disk, following symlinks. Paths outside the root, e.g. from absolute
+    /// patterns, are returned as they are.
+    fn walk(&self, dir: &str, options: &WalkOptions) -> Result<Vec<String>, LintError> {
+        let walk_root = self.root.join(dir);
+        if !walk_root.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let ignore_filter = options.ignore.clone();
+        let filter_root = self.root.clone();
+        let mut walker = WalkBuilder::new(&walk_root);
+        walker
+            .standard_filters(options.respect_ignore_files)
+            .follow_links(true)
+            .max_depth(options.max_depth)
+            .filter_entry(move |entry| {
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                let relative = entry.path().strip_prefix(&filter_root).unwrap_or(entry.path());
+                !is_ignored_entry(&ignore_filter, relative, is_dir)
+            });
+
+        let mut files = Vec::new();
+        for entry in walker.build() {
+            let entry = entry.map_err(|e| LintError::Walk(e.to_string()))?;
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(&self.root).unwrap_or(entry.path());
+            files.push(relative.display().to_string());
+        }
+        Ok(files)
+    }
+}
+
+/// Returns true if a walked entry is excluded. Parents have already been checked by the walk.
+fn is_ignored_entry(ignore: &Gitignore, path: &Path, is_dir: bool) -> bool {
+    if path.has_root() {
+        return false;
+    }
+    ignore.matched(path, is_dir).is_ignore()
+}
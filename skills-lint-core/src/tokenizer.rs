@@ -1,7 +1,72 @@
-use tiktoken_rs::CoreBPE;
+use std::sync::OnceLock;
+
+use sha2::{Digest, Sha256};
+use tiktoken_rs::{CoreBPE, Rank};
 
 use crate::errors::LintError;
 
+/// Names of all supported encodings.
+pub const ENCODINGS: &[&str] = &["cl100k_base", "o200k_base", "p50k_base", "r50k_base"];
+
+/// Text whose encoding is part of the fingerprint, to cover how each encoding splits text
+/// before merging: contractions, numbers, punctuation, whitespace runs and special tokens.
+const SAMPLE: &str = "It's 2024: naïve café — 1234567 tokens!\n\n  \tdon't   stop<|endoftext|>日本語 `fn main() {}`\r\n";
+
+/// Consecutive unknown ranks after which the vocabulary is taken to end. Special tokens
+/// follow the regular ones after a gap of a few ranks.
+const MAX_RANK_GAP: Rank = 1024;
+
+/// Short hash identifying the tokenizer used for `encoding`.
+///
+/// It covers the encoding's vocabulary, how it encodes a sample text and the skills-lint
+/// version, so cached counts are not reused after any tokenizer change or a skills-lint
+/// upgrade. It is computed once per encoding.
+pub fn fingerprint(encoding: &str) -> String {
+    static FINGERPRINTS: [OnceLock<String>; ENCODINGS.len()] = [const { OnceLock::new() }; ENCODINGS.len()];
+    match ENCODINGS.iter().position(|&name| name == encoding) {
+        Some(index) => FINGERPRINTS[index].get_or_init(|| compute_fingerprint(encoding)).clone(),
+        None => compute_fingerprint(encoding),
+    }
+}
+
+fn compute_fingerprint(encoding: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("skills-lint {}\0{encoding}\0", env!("CARGO_PKG_VERSION")));
+    if let Ok(bpe) = encoder(encoding) {
+        let mut gap = 0;
+        let mut rank: Rank = 0;
+        while gap < MAX_RANK_GAP {
+            match token_bytes(bpe, rank) {
+                Some(bytes) => {
+                    gap = 0;
+                    hasher.update(rank.to_le_bytes());
+                    hasher.update((bytes.len() as u64).to_le_bytes());
+                    hasher.update(bytes);
+                }
+                None => gap += 1,
+            }
+            rank += 1;
+        }
+        for token in bpe.encode_with_special_tokens(SAMPLE) {
+            hasher.update(token.to_le_bytes());
+        }
+    }
+    format!("{:x}", hasher.finalize())[..12].to_string()
+}
+
+/// Bytes of the token with `rank`, or `None` if the encoding has no such token.
+///
+/// `CoreBPE` does not expose its rank table and its raw decoder panics on unknown ranks.
+/// `decode` fails both on an unknown rank and on a token that is only part of a UTF-8
+/// character, so only the latter are decoded raw.
+fn token_bytes(bpe: &CoreBPE, rank: Rank) -> Option<Vec<u8>> {
+    match bpe.decode(vec![rank]) {
+        Ok(text) => Some(text.into_bytes()),
+        Err(e) if e.to_string().contains("UTF-8") => bpe._decode_native_and_split(vec![rank]).next(),
+        Err(_) => None,
+    }
+}
+
 /// Get a tiktoken BPE encoder by encoding name.
 pub fn get_encoding(name: &str) -> Result<CoreBPE, LintError> {
     match name {
@@ -14,9 +79,22 @@ pub fn get_encoding(name: &str) -> Result<CoreBPE, LintError> {
     .map_err(|e| LintError::Tokenizer(e.to_string()))
 }
 
+/// The encoder for `name`, loaded once per process.
+fn encoder(name: &str) -> Result<&'static CoreBPE, LintError> {
+    static ENCODERS: [OnceLock<Result<CoreBPE, LintError>>; ENCODINGS.len()] = [const { OnceLock::new() }; ENCODINGS.len()];
+    let index = ENCODINGS
+        .iter()
+        .position(|&encoding| encoding == name)
+        .ok_or_else(|| LintError::UnknownEncoding(name.to_string()))?;
+    ENCODERS[index].get_or_init(|| get_encoding(name)).as_ref().map_err(|e| match e {
+        LintError::Tokenizer(message) => LintError::Tokenizer(message.clone()),
+        e => LintError::Tokenizer(e.to_string()),
+    })
+}
+
 /// Count tokens in the given text using the specified encoding name.
 pub fn count_tokens(text: &str, encoding_name: &str) -> Result<usize, LintError> {
-    let bpe = get_encoding(encoding_name)?;
+    let bpe = encoder(encoding_name)?;
     Ok(bpe.encode_with_special_tokens(text).len())
 }
 
@@ -36,6 +114,33 @@ mod tests {
         assert!(count > 0);
     }
 
+    #[test]
+    fn test_all_listed_encodings_load() {
+        for name in ENCODINGS {
+            assert!(get_encoding(name).is_ok(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint("cl100k_base").len(), 12);
+        assert_eq!(fingerprint("cl100k_base"), compute_fingerprint("cl100k_base"));
+        assert_ne!(fingerprint("cl100k_base"), fingerprint("o200k_base"));
+        assert_ne!(fingerprint("p50k_base"), fingerprint("r50k_base"));
+        assert_eq!(fingerprint("nonexistent_encoding").len(), 12);
+    }
+
+    #[test]
+    fn test_token_bytes_include_partial_characters() {
+        let bpe = get_encoding("cl100k_base").unwrap();
+        // The first 256 ranks are the single bytes, most of which are not valid UTF-8 alone.
+        let mut bytes: Vec<u8> = (0..256).flat_map(|rank| token_bytes(&bpe, rank).unwrap()).collect();
+        bytes.sort_unstable();
+        assert_eq!(bytes, (0..=255).collect::<Vec<u8>>());
+        assert_eq!(token_bytes(&bpe, 100257), Some(b"<|endoftext|>".to_vec()));
+        assert_eq!(token_bytes(&bpe, 100256), None);
+    }
+
     #[test]
     fn test_unknown_encoding() {
         let result = count_tokens("test", "nonexistent_encoding");
@@ -1,7 +1,10 @@
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 /// Severity level for a lint finding.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Pass,
     Warning,
@@ -19,7 +22,7 @@ impl fmt::Display for Severity {
 }
 
 /// A single lint finding for one file × model combination.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LintFinding {
     pub rule: String,
     pub file: String,
@@ -31,7 +34,7 @@ pub struct LintFinding {
 }
 
 /// A structural validation finding for one file.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StructureFinding {
     pub rule: String,
     pub file: String,
@@ -39,6 +42,14 @@ pub struct StructureFinding {
     pub severity: Severity,
 }
 
+/// Results of the per-file rules for one file: token, size and frontmatter findings, and
+/// the `skill-structure` finding if the rule is enabled.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FileResult {
+    pub findings: Vec<LintFinding>,
+    pub structure: Option<StructureFinding>,
+}
+
 /// Aggregated results from a lint run.
 #[derive(Debug, Clone)]
 pub struct LintReport {
@@ -0,0 +1,133 @@
+use std::process;
+
+use colored::Colorize;
+use serde_json::json;
+use skills_lint_core::cache::{CacheMode, CacheStats, TokenCache};
+use skills_lint_core::config::Config;
+use skills_lint_core::errors::LintError;
+use skills_lint_core::lint;
+use skills_lint_core::rules::skill_index_budget;
+use skills_lint_core::source::OsSource;
+
+use crate::cli::{CacheCommand, OutputFormat};
+use crate::table::format_number;
+
+/// Shown after what `prune` and `clear` would have done in read-only mode.
+const READ_ONLY_NOTE: &str = "(read-only cache mode, nothing written)";
+
+/// Run a `skills-lint cache` subcommand against the cache directory of `config`. In
+/// read-only mode, `prune` and `clear` report what they would remove without writing.
+pub fn run(config: &Config, action: &CacheCommand, mode: CacheMode) {
+    let dir = config.cache_dir();
+    let result = match *action {
+        CacheCommand::Stats { format } => {
+            print_stats(&TokenCache::load(&dir).stats(), format);
+            Ok(())
+        }
+        CacheCommand::Prune { runs } => prune(config, runs, mode),
+        CacheCommand::Clear if mode == CacheMode::ReadOnly => {
+            let path = TokenCache::load(&dir).path();
+            if path.exists() {
+                println!("Would remove {} {}", path.display(), READ_ONLY_NOTE.dimmed());
+            } else {
+                println!("{}", format!("No cache in {}", dir.display()).dimmed());
+            }
+            Ok(())
+        }
+        CacheCommand::Clear => TokenCache::clear(&dir).map(|existed| {
+            let path = dir.display();
+            if existed {
+                println!("Removed cache file in {path}");
+            } else {
+                println!("{}", format!("No cache in {path}").dimmed());
+            }
+        }),
+    };
+    if let Err(e) = result {
+        eprintln!("{} {e}", "error:".red().bold());
+        process::exit(3);
+    }
+}
+
+/// Drop entries not used in the last `runs` runs, or, without `runs`, entries the
+/// current files did not need when linted with the cache.
+fn prune(config: &Config, runs: Option<u64>, mode: CacheMode) -> Result<(), LintError> {
+    // Pruning needs the existing entries, so write-only mode still reads them.
+    let read_only = mode == CacheMode::ReadOnly;
+    let mode = if read_only { CacheMode::ReadOnly } else { CacheMode::ReadWrite };
+    let mut cache = TokenCache::open(&config.cache_dir(), mode);
+    let removed = match runs {
+        Some(runs) => cache.prune(runs),
+        None => {
+            let source = OsSource::new(&config.root);
+            let files = lint::discover(config, &source)?;
+            let contents = lint::read_files(&source, &files)?;
+            for (file, content) in files.iter().zip(&contents) {
+                lint::lint_content(config, file, content, Some(&mut cache))?;
+            }
+            skill_index_budget::check_all(config, &contents, Some(&mut cache))?;
+            cache.prune(1)
+        }
+    };
+    cache.flush()?;
+    let stats = cache.stats();
+    let entries = if removed == 1 { "entry" } else { "entries" };
+    let left = format_number(stats.entries + stats.results);
+    if read_only {
+        println!("Would remove {} {entries}, {left} left {}", format_number(removed), READ_ONLY_NOTE.dimmed());
+    } else {
+        println!("Removed {} {entries}, {left} left", format_number(removed));
+    }
+    Ok(())
+}
+
+fn print_stats(stats: &CacheStats, format: OutputFormat) {
+    let hit_rate = stats.last_run.hit_rate();
+    match format {
+        OutputFormat::Json => {
+            let mut value = serde_json::to_value(stats).expect("failed to serialize cache stats");
+            value["lastRun"]["hitRate"] = json!(hit_rate);
+            println!("{}", serde_json::to_string_pretty(&value).expect("failed to serialize cache stats"));
+        }
+        OutputFormat::Table => {
+            let hit_rate = match hit_rate {
+                Some(rate) => format!(
+                    "{:.1}% ({} hits, {} misses)",
+                    rate * 100.0,
+                    format_number(stats.last_run.hits as usize),
+                    format_number(stats.last_run.misses as usize)
+                ),
+                None => "—".to_string(),
+            };
+            println!();
+            println!("  {}", stats.path.display().to_string().bold());
+            println!();
+            println!("  {:<10} {}", "Entries", format_number(stats.entries));
+            println!("  {:<10} {}", "Results", format_number(stats.results));
+            println!("  {:<10} {}", "Size", format_size(stats.size_bytes));
+            println!("  {:<10} {}", "Runs", format_number(stats.runs as usize));
+            println!("  {:<10} {hit_rate}", "Last run");
+            println!();
+        }
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1_048_576), "3.0 MiB");
+    }
+}
@@ -1,4 +1,6 @@
-use clap::{Parser, Subcommand};
+use clap::builder::BoolishValueParser;
+use clap::{Parser, Subcommand, ValueEnum};
+use skills_lint_core::cache::CacheMode;
 
 #[derive(Parser, Debug)]
 #[command(name = "skills-lint", version, about = "Lint agent skill markdown files against per-model token budgets")]
@@ -10,20 +12,60 @@ pub struct Cli {
     #[arg(long)]
     pub file: Option<String>,
 
-    /// Config file path
-    #[arg(long, default_value = ".skills-lint.config.json")]
-    pub config: String,
+    /// Lint the files of a git revision, e.g. origin/main, instead of the working tree
+    #[arg(long, value_name = "REV")]
+    pub rev: Option<String>,
+
+    /// Exclude files matching a gitignore-style pattern (repeatable)
+    #[arg(long, value_name = "PATTERN", global = true)]
+    pub ignore: Vec<String>,
+
+    /// Also search hidden and .gitignore'd paths
+    #[arg(long, global = true, env = "SKILLS_LINT_NO_IGNORE_FILES", value_parser = BoolishValueParser::new())]
+    pub no_ignore_files: bool,
+
+    /// Config file path (default: search upwards from the current directory)
+    #[arg(long, global = true, env = "SKILLS_LINT_CONFIG")]
+    pub config: Option<String>,
+
+    /// Set a config value, e.g. token-limit.gpt-4o.error=12000 (repeatable)
+    #[arg(long = "rule", value_name = "KEY=VALUE", global = true)]
+    pub rules: Vec<String>,
+
+    /// Only check these models (repeatable)
+    #[arg(long = "model", value_name = "MODEL", global = true, env = "SKILLS_LINT_MODEL", value_delimiter = ',')]
+    pub models: Vec<String>,
+
+    /// Only run these rules (repeatable)
+    #[arg(long = "only-rule", value_name = "RULE", global = true)]
+    pub only_rules: Vec<String>,
+
+    /// Do not run these rules (repeatable)
+    #[arg(long = "disable-rule", value_name = "RULE", global = true)]
+    pub disable_rules: Vec<String>,
 
     /// Suppress the ASCII banner (for CI)
-    #[arg(long)]
+    #[arg(long, env = "SKILLS_LINT_QUIET", value_parser = BoolishValueParser::new())]
     pub quiet: bool,
 
     /// Show all findings including passing rules
-    #[arg(long)]
+    #[arg(long, env = "SKILLS_LINT_VERBOSE", value_parser = BoolishValueParser::new())]
     pub verbose: bool,
 
-    /// Disable token-count caching
+    /// Estimate the input cost of loading each skill from configured prices
     #[arg(long)]
+    pub cost: bool,
+
+    /// Cache directory, or "user" for the shared per-user cache (default: next to the config)
+    #[arg(long, value_name = "DIR", global = true, env = "SKILLS_LINT_CACHE_LOCATION")]
+    pub cache_location: Option<String>,
+
+    /// How to use the cache: read-write (default), read-only or write-only
+    #[arg(long, value_enum, global = true, env = "SKILLS_LINT_CACHE_MODE", default_value_t = CacheModeArg::ReadWrite)]
+    pub cache_mode: CacheModeArg,
+
+    /// Disable token-count caching
+    #[arg(long, global = true, env = "SKILLS_LINT_NO_CACHE", value_parser = BoolishValueParser::new())]
     pub no_cache: bool,
 }
 
@@ -31,4 +73,90 @@ pub struct Cli {
 pub enum Command {
     /// Initialize a new .skills-lint.config.json
     Init,
+    /// Print the JSON Schema for the config file
+    Schema,
+    /// Show the rules, budgets and overrides that apply to a file
+    PrintConfig {
+        /// Skill file to resolve the config for
+        file: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Show how tokens and lint results changed between two git revisions
+    Diff {
+        /// Revision to compare against, e.g. origin/main
+        base: String,
+        /// Revision to compare (default: the working tree)
+        head: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = DiffFormat::Table)]
+        format: DiffFormat,
+    },
+    /// Inspect and maintain the token cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// Show the entry count, size and hit rate of the last run
+    Stats {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Drop entries the current files no longer need
+    Prune {
+        /// Instead drop entries not used in the last N runs, without linting
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+        runs: Option<u64>,
+    },
+    /// Delete the cache file
+    Clear,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffFormat {
+    Table,
+    Json,
+    Markdown,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheModeArg {
+    ReadWrite,
+    ReadOnly,
+    WriteOnly,
+}
+
+impl From<CacheModeArg> for CacheMode {
+    fn from(mode: CacheModeArg) -> Self {
+        match mode {
+            CacheModeArg::ReadWrite => CacheMode::ReadWrite,
+            CacheModeArg::ReadOnly => CacheMode::ReadOnly,
+            CacheModeArg::WriteOnly => CacheMode::WriteOnly,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovery_flags_follow_subcommands() {
+        let cli = Cli::try_parse_from(["skills-lint", "diff", "HEAD", "--ignore", "vendor/", "--no-ignore-files"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Diff { ref base, head: None, .. }) if base == "HEAD"));
+        assert_eq!(cli.ignore, vec!["vendor/"]);
+        assert!(cli.no_ignore_files);
+    }
 }
@@ -0,0 +1,97 @@
+use colored::Colorize;
+use comfy_table::modifiers::UTF8_ROUND_CORNERS;
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Attribute, Cell, CellAlignment, ContentArrangement, Table};
+use skills_lint_core::cost::ModelCost;
+use skills_lint_core::rules::skill_index_budget::AGGREGATE_LABEL;
+
+/// Label of the row that sums loading every skill once.
+const TOTAL_LABEL: &str = "(all skills)";
+
+/// Format a USD amount, keeping enough decimals for sub-cent costs.
+fn format_cost(usd: f64) -> String {
+    if usd == 0.0 || usd >= 1.0 {
+        format!("${usd:.2}")
+    } else if usd >= 0.01 {
+        format!("${usd:.4}")
+    } else {
+        format!("${usd:.6}")
+    }
+}
+
+fn cost_cell(cost: &ModelCost, tokens: usize) -> Cell {
+    Cell::new(format_cost(cost.cost(tokens))).set_alignment(CellAlignment::Right)
+}
+
+/// Print estimated input costs per skill load, for the skill index and for all skills.
+pub fn print_costs(files: &[String], costs: &[ModelCost]) {
+    println!();
+    println!("  {}", "Estimated input cost".bold());
+    println!();
+
+    if costs.is_empty() {
+        println!(
+            "  {}",
+            "No prices configured. Add a \"pricing\" section to the config to estimate costs.".dimmed()
+        );
+        println!();
+        return;
+    }
+
+    let mut header = vec![Cell::new("Skill").add_attribute(Attribute::Bold)];
+    header.extend(costs.iter().map(|cost| {
+        Cell::new(format!("{} (${}/1M)", cost.model, cost.price))
+            .set_alignment(CellAlignment::Right)
+            .add_attribute(Attribute::Bold)
+    }));
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL_CONDENSED)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(header);
+
+    for (i, file) in files.iter().enumerate() {
+        let mut row = vec![Cell::new(file.strip_prefix("./").unwrap_or(file))];
+        row.extend(costs.iter().map(|cost| cost_cell(cost, cost.file_tokens[i])));
+        table.add_row(row);
+    }
+
+    if costs.iter().any(|cost| cost.index_tokens.is_some()) {
+        let mut row = vec![Cell::new(AGGREGATE_LABEL).add_attribute(Attribute::Italic)];
+        row.extend(costs.iter().map(|cost| cost_cell(cost, cost.index_tokens.unwrap_or(0))));
+        table.add_row(row);
+    }
+
+    let mut row = vec![Cell::new(TOTAL_LABEL).add_attribute(Attribute::Bold)];
+    row.extend(costs.iter().map(|cost| cost_cell(cost, cost.total_tokens()).add_attribute(Attribute::Bold)));
+    table.add_row(row);
+
+    for line in table.to_string().lines() {
+        println!("  {line}");
+    }
+    println!();
+    println!(
+        "  {}",
+        format!(
+            "Per skill: cost of loading it once. {AGGREGATE_LABEL}: added to every conversation. \
+             {TOTAL_LABEL}: loading every skill once."
+        )
+        .dimmed()
+    );
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_cost() {
+        assert_eq!(format_cost(0.0), "$0.00");
+        assert_eq!(format_cost(0.000_512_5), "$0.000513");
+        assert_eq!(format_cost(0.25), "$0.2500");
+        assert_eq!(format_cost(12.345), "$12.35");
+    }
+}
@@ -0,0 +1,306 @@
+use std::process;
+
+use colored::Colorize;
+use comfy_table::modifiers::UTF8_ROUND_CORNERS;
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Attribute, Cell, CellAlignment, ContentArrangement, Table};
+use serde_json::json;
+use skills_lint_core::cache::{Cache, TokenCache};
+use skills_lint_core::config::Config;
+use skills_lint_core::diff::{FileStatus, LintDiff, TokenChange, TreeLint};
+use skills_lint_core::errors::LintError;
+use skills_lint_core::rules::skill_index_budget::AGGREGATE_LABEL;
+use skills_lint_core::source::{GitSource, OsSource, SkillSource};
+use skills_lint_core::types::Severity;
+
+use crate::cli::DiffFormat;
+use crate::table::format_number;
+
+/// Label of the row that sums every skill.
+const TOTAL_LABEL: &str = "(all skills)";
+
+/// Label of the head side when no head revision is given.
+const WORKING_TREE: &str = "working tree";
+
+/// One side of the comparison.
+struct Side {
+    /// Revision as given, or None for the working tree.
+    rev: Option<String>,
+    commit: Option<String>,
+    lint: TreeLint,
+}
+
+impl Side {
+    fn label(&self) -> String {
+        match (&self.rev, &self.commit) {
+            (Some(rev), Some(commit)) => format!("{rev} ({})", &commit[..12]),
+            _ => WORKING_TREE.to_string(),
+        }
+    }
+}
+
+/// Lint `base` and `head` (the working tree if None) from the local repository and print
+/// how tokens and results changed.
+pub fn run(config: &Config, base: &str, head: Option<&str>, format: DiffFormat, mut cache: Option<TokenCache>) {
+    let mut lint_side = |rev: Option<&str>| -> Result<Side, LintError> {
+        let (source, commit): (Box<dyn SkillSource>, _) = match rev {
+            Some(rev) => {
+                let source = GitSource::open(&config.root, rev)?;
+                let commit = source.commit().to_string();
+                (Box::new(source), Some(commit))
+            }
+            None => (Box::new(OsSource::new(&config.root)), None),
+        };
+        let lint = TreeLint::lint(config, &*source, cache.as_mut().map(|c| c as &mut dyn Cache))?;
+        Ok(Side {
+            rev: rev.map(str::to_string),
+            commit,
+            lint,
+        })
+    };
+    let sides = lint_side(Some(base)).and_then(|base| Ok((base, lint_side(head)?)));
+    let (base, head) = match sides {
+        Ok(sides) => sides,
+        Err(e) => {
+            eprintln!("{} {e}", "error:".red().bold());
+            process::exit(3);
+        }
+    };
+
+    if let Some(ref c) = cache {
+        if let Err(e) = c.flush() {
+            eprintln!("{} {e}", "warning:".yellow().bold());
+        }
+    }
+
+    let diff = LintDiff::between(&base.lint, &head.lint);
+    match format {
+        DiffFormat::Table => print_table(&diff, &base, &head),
+        DiffFormat::Markdown => print!("{}", markdown(&diff, &base, &head)),
+        DiffFormat::Json => {
+            let mut output = serde_json::to_value(&diff).expect("failed to serialize diff");
+            output["base"] = json!({ "rev": base.rev, "commit": base.commit });
+            output["head"] = json!({ "rev": head.rev, "commit": head.commit });
+            println!("{}", serde_json::to_string_pretty(&output).expect("failed to serialize diff"));
+        }
+    }
+}
+
+/// Format a token delta with its sign, e.g. `+1,240` or `-12`.
+fn format_delta(delta: i64) -> String {
+    let magnitude = format_number(delta.unsigned_abs() as usize);
+    match delta {
+        0 => "0".to_string(),
+        d if d > 0 => format!("+{magnitude}"),
+        _ => format!("-{magnitude}"),
+    }
+}
+
+/// Describe a change as its delta followed by the counts before and after, e.g.
+/// `+120 (1,000 → 1,120)`. A missing side is shown as `—`.
+fn format_change(change: &TokenChange) -> String {
+    if change.base.is_none() && change.head.is_none() {
+        return "—".to_string();
+    }
+    let side = |tokens: Option<usize>| tokens.map_or("—".to_string(), format_number);
+    format!(
+        "{} ({} → {})",
+        format_delta(change.delta),
+        side(change.base),
+        side(change.head)
+    )
+}
+
+fn status_text(status: FileStatus) -> &'static str {
+    match status {
+        FileStatus::Added => "added",
+        FileStatus::Removed => "removed",
+        FileStatus::Modified => "modified",
+    }
+}
+
+fn colored_file_status(status: FileStatus) -> String {
+    let text = status_text(status);
+    match status {
+        FileStatus::Added => text.green().to_string(),
+        FileStatus::Removed => text.red().to_string(),
+        FileStatus::Modified => text.yellow().to_string(),
+    }
+}
+
+/// Shown for the side of a transition where the finding does not exist.
+const NO_FINDING: &str = "none";
+
+fn severity_text(severity: Option<Severity>) -> String {
+    severity.map_or(NO_FINDING.to_string(), |severity| severity.to_string())
+}
+
+fn colored_severity(severity: Option<Severity>) -> String {
+    let text = severity_text(severity);
+    match severity {
+        None => text.dimmed().to_string(),
+        Some(Severity::Pass) => text.green().to_string(),
+        Some(Severity::Warning) => text.yellow().bold().to_string(),
+        Some(Severity::Error) => text.red().bold().to_string(),
+    }
+}
+
+fn summary(diff: &LintDiff) -> String {
+    format!(
+        "{} added, {} removed, {} modified",
+        diff.count(FileStatus::Added),
+        diff.count(FileStatus::Removed),
+        diff.count(FileStatus::Modified)
+    )
+}
+
+fn print_table(diff: &LintDiff, base: &Side, head: &Side) {
+    println!();
+    println!("  {}", format!("{} → {}", base.label(), head.label()).bold());
+    println!();
+
+    if diff.is_empty() {
+        println!("  {}", "No changes to skills.".dimmed());
+        println!();
+        return;
+    }
+
+    let mut header = vec![
+        Cell::new("Skill").add_attribute(Attribute::Bold),
+        Cell::new("Status").add_attribute(Attribute::Bold),
+    ];
+    header.extend(diff.models.iter().map(|model| {
+        Cell::new(model)
+            .set_alignment(CellAlignment::Right)
+            .add_attribute(Attribute::Bold)
+    }));
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL_CONDENSED)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(header);
+
+    let change_cells = |changes: &[TokenChange]| -> Vec<Cell> {
+        changes
+            .iter()
+            .map(|change| Cell::new(format_change(change)).set_alignment(CellAlignment::Right))
+            .collect::<Vec<_>>()
+    };
+    for file in &diff.files {
+        let mut row = vec![Cell::new(&file.file), Cell::new(colored_file_status(file.status))];
+        row.extend(change_cells(&file.tokens));
+        table.add_row(row);
+    }
+    if !diff.index.is_empty() {
+        let mut row = vec![Cell::new(AGGREGATE_LABEL).add_attribute(Attribute::Italic), Cell::new("")];
+        row.extend(change_cells(&diff.index));
+        table.add_row(row);
+    }
+    let mut row = vec![Cell::new(TOTAL_LABEL).add_attribute(Attribute::Bold), Cell::new("")];
+    row.extend(change_cells(&diff.total).into_iter().map(|cell| cell.add_attribute(Attribute::Bold)));
+    table.add_row(row);
+
+    for line in table.to_string().lines() {
+        println!("  {line}");
+    }
+
+    if !diff.transitions.is_empty() {
+        println!();
+        println!("  {}", "State changes".bold());
+        println!();
+        for transition in &diff.transitions {
+            let model = transition.model.as_deref().map(|m| format!(" ({m})")).unwrap_or_default();
+            println!(
+                "  {} {}{model}: {} → {}",
+                transition.file,
+                transition.rule.dimmed(),
+                colored_severity(transition.from),
+                colored_severity(transition.to)
+            );
+        }
+    }
+
+    println!();
+    println!("  {}", summary(diff).dimmed());
+    println!();
+}
+
+/// Render the diff as a markdown section, e.g. for a pull request comment.
+fn markdown(diff: &LintDiff, base: &Side, head: &Side) -> String {
+    let side = |side: &Side| match (&side.rev, &side.commit) {
+        (Some(rev), Some(commit)) => format!("`{rev}` (`{}`)", &commit[..12]),
+        _ => WORKING_TREE.to_string(),
+    };
+    let mut out = format!("### Skill token changes\n\n{} → {}\n\n", side(base), side(head));
+
+    if diff.is_empty() {
+        out.push_str("No changes to skills.\n");
+        return out;
+    }
+
+    let columns = diff.models.len();
+    out.push_str(&format!("| Skill | Status |{}\n", diff.models.iter().map(|m| format!(" {m} |")).collect::<String>()));
+    out.push_str(&format!("| --- | --- |{}\n", " ---: |".repeat(columns)));
+    let cells = |changes: &[TokenChange], bold: bool| -> String {
+        changes
+            .iter()
+            .map(|change| match bold {
+                true => format!(" **{}** |", format_change(change)),
+                false => format!(" {} |", format_change(change)),
+            })
+            .collect()
+    };
+    for file in &diff.files {
+        out.push_str(&format!(
+            "| `{}` | {} |{}\n",
+            file.file,
+            status_text(file.status),
+            cells(&file.tokens, false)
+        ));
+    }
+    if !diff.index.is_empty() {
+        out.push_str(&format!("| _{AGGREGATE_LABEL}_ | |{}\n", cells(&diff.index, false)));
+    }
+    out.push_str(&format!("| **{TOTAL_LABEL}** | |{}\n", cells(&diff.total, true)));
+
+    if !diff.transitions.is_empty() {
+        out.push_str("\n#### State changes\n\n| Skill | Rule | Model | Before | After |\n| --- | --- | --- | --- | --- |\n");
+        for transition in &diff.transitions {
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} |\n",
+                transition.file,
+                transition.rule,
+                transition.model.as_deref().unwrap_or(""),
+                severity_text(transition.from),
+                severity_text(transition.to)
+            ));
+        }
+    }
+
+    out.push_str(&format!("\n{}\n", summary(diff)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_change() {
+        assert_eq!(format_delta(1240), "+1,240");
+        assert_eq!(format_delta(-12), "-12");
+        assert_eq!(format_delta(0), "0");
+
+        let change = |base, head: Option<usize>| TokenChange {
+            model: "gpt-4o".to_string(),
+            base,
+            head,
+            delta: head.unwrap_or(0) as i64 - base.unwrap_or(0) as i64,
+        };
+        assert_eq!(format_change(&change(Some(1000), Some(1120))), "+120 (1,000 → 1,120)");
+        assert_eq!(format_change(&change(None, Some(80))), "+80 (— → 80)");
+        assert_eq!(format_change(&change(None, None)), "—");
+    }
+}
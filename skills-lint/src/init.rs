@@ -4,6 +4,8 @@ use std::process;
 use colored::Colorize;
 use dialoguer::{Confirm, Input, MultiSelect};
 use serde_json::{json, Map, Value};
+use skills_lint_core::config::presets::{build_model_budgets, DEFAULT_PATTERN};
+use skills_lint_core::config::schema::SCHEMA_URL;
 
 const CONFIG_PATH: &str = ".skills-lint.config.json";
 
@@ -16,7 +18,8 @@ const MODELS: &[&str] = &[
     "gpt-3.5-turbo",
 ];
 
-const OPTIONAL_RULES: &[&str] = &[
+const RULES: &[&str] = &[
+    "token-limit",
     "frontmatter-limit",
     "skill-index-budget",
     "skill-structure",
@@ -24,42 +27,6 @@ const OPTIONAL_RULES: &[&str] = &[
     "unique-description",
 ];
 
-struct ModelBudgets {
-    token_limit: (usize, usize),
-    frontmatter_limit: (usize, usize),
-    skill_index_budget: (usize, usize),
-}
-
-fn default_budgets(model: &str) -> ModelBudgets {
-    match model {
-        "gpt-5" => ModelBudgets {
-            token_limit: (16000, 32000),
-            frontmatter_limit: (2000, 4000),
-            skill_index_budget: (4000, 8000),
-        },
-        "gpt-4o" | "gpt-4o-mini" | "gpt-4-turbo" => ModelBudgets {
-            token_limit: (8000, 16000),
-            frontmatter_limit: (1000, 2000),
-            skill_index_budget: (2000, 4000),
-        },
-        "gpt-4" => ModelBudgets {
-            token_limit: (2000, 4000),
-            frontmatter_limit: (500, 1000),
-            skill_index_budget: (1000, 2000),
-        },
-        "gpt-3.5-turbo" => ModelBudgets {
-            token_limit: (4000, 8000),
-            frontmatter_limit: (500, 1000),
-            skill_index_budget: (1000, 2000),
-        },
-        _ => ModelBudgets {
-            token_limit: (8000, 16000),
-            frontmatter_limit: (1000, 2000),
-            skill_index_budget: (2000, 4000),
-        },
-    }
-}
-
 pub fn run() {
     println!();
     println!(
@@ -85,7 +52,7 @@ pub fn run() {
     // 2. Glob pattern
     let pattern: String = Input::new()
         .with_prompt("Glob pattern for skill files")
-        .default("./.github/**/SKILL.md".to_string())
+        .default(DEFAULT_PATTERN.to_string())
         .interact_text()
         .unwrap_or_else(|_| {
             eprintln!("{} failed to read input", "error:".red().bold());
@@ -116,15 +83,23 @@ pub fn run() {
     // 4. Rules (all enabled by default)
     let rule_selections = MultiSelect::new()
         .with_prompt("Rules (space to toggle, enter to confirm)")
-        .items(OPTIONAL_RULES)
-        .defaults(&[true; 5])
+        .items(RULES)
+        .defaults(&[true; 6])
         .interact()
         .unwrap_or_else(|_| {
             eprintln!("{} failed to read input", "error:".red().bold());
             process::exit(1);
         });
 
-    let selected_rules: Vec<&str> = rule_selections.iter().map(|&i| OPTIONAL_RULES[i]).collect();
+    if rule_selections.is_empty() {
+        eprintln!(
+            "{} at least one rule must be selected",
+            "error:".red().bold()
+        );
+        process::exit(1);
+    }
+
+    let selected_rules: Vec<&str> = rule_selections.iter().map(|&i| RULES[i]).collect();
 
     // 5. Build config
     let config = build_config(&pattern, &selected_models, &selected_rules);
@@ -150,17 +125,17 @@ pub fn run() {
     }
 }
 
-fn build_config(pattern: &str, models: &[&str], optional_rules: &[&str]) -> Value {
+fn build_config(pattern: &str, models: &[&str], selected_rules: &[&str]) -> Value {
     let mut rules = Map::new();
 
-    // token-limit is always included
-    rules.insert(
-        "token-limit".to_string(),
-        build_model_budgets(models, |b| b.token_limit),
-    );
-
-    for &rule in optional_rules {
+    for &rule in selected_rules {
         match rule {
+            "token-limit" => {
+                rules.insert(
+                    "token-limit".to_string(),
+                    build_model_budgets(models, |b| b.token_limit),
+                );
+            }
             "frontmatter-limit" => {
                 rules.insert(
                     "frontmatter-limit".to_string(),
@@ -187,6 +162,7 @@ fn build_config(pattern: &str, models: &[&str], optional_rules: &[&str]) -> Valu
     }
 
     json!({
+        "$schema": SCHEMA_URL,
         "patterns": [pattern],
         "rules": rules,
     })
@@ -240,22 +216,3 @@ fn update_gitignore() {
         }
     }
 }
-
-fn build_model_budgets(
-    models: &[&str],
-    extract: fn(&ModelBudgets) -> (usize, usize),
-) -> Value {
-    let mut model_map = Map::new();
-    for &model in models {
-        let budgets = default_budgets(model);
-        let (warning, error) = extract(&budgets);
-        model_map.insert(
-            model.to_string(),
-            json!({
-                "warning": warning,
-                "error": error,
-            }),
-        );
-    }
-    json!({ "models": model_map })
-}
@@ -1,19 +1,26 @@
 mod banner;
+mod cache;
 mod cli;
+mod cost_table;
+mod diff;
 mod init;
+mod print_config;
 mod table;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process;
 
 use clap::Parser;
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
-use skills_lint_core::cache::TokenCache;
-use skills_lint_core::config::Config;
-use skills_lint_core::lint;
-use skills_lint_core::rules::{skill_index_budget, unique_fields};
-use skills_lint_core::types::{LintFinding, LintReport, Severity, StructureFinding};
+use skills_lint_core::cache::{Cache, TokenCache, USER_CACHE_LOCATION};
+use skills_lint_core::config::flags::ConfigFlags;
+use skills_lint_core::config::{self, Config};
+use skills_lint_core::cost;
+use skills_lint_core::errors::LintError;
+use skills_lint_core::lint::{self, Linter};
+use skills_lint_core::source::{GitSource, OsSource, SkillSource};
+use skills_lint_core::types::Severity;
 
 use cli::{Cli, Command};
 
@@ -22,28 +29,113 @@ fn main() {
 
     match args.command {
         Some(Command::Init) => init::run(),
+        Some(Command::Schema) => print!("{}", config::schema::config_schema_string()),
+        Some(Command::PrintConfig { ref file, format }) => {
+            let (config_path, config) = load_config(&args);
+            print_config::run(&config, &config_path, file, format);
+        }
+        Some(Command::Diff {
+            ref base,
+            ref head,
+            format,
+        }) => {
+            let (_, mut config) = load_config(&args);
+            apply_discovery_flags(&args, &mut config);
+            let cache = open_cache(&args, &config);
+            diff::run(&config, base, head.as_deref(), format, cache);
+        }
+        Some(Command::Cache { ref action }) => {
+            let (_, config) = load_config(&args);
+            cache::run(&config, action, args.cache_mode.into());
+        }
         None => run_lint(args),
     }
 }
 
+/// Locate and load the config with any command-line overrides, exiting with a runtime error if it cannot be loaded.
+fn load_config(args: &Cli) -> (PathBuf, Config) {
+    let config_path = match args.config {
+        Some(ref path) => Ok(PathBuf::from(path)),
+        None => std::env::current_dir()
+            .map_err(|e| LintError::ConfigRead(".".to_string(), e))
+            .and_then(|cwd| config::locate(&cwd)),
+    };
+    let flags = ConfigFlags {
+        rules: args.rules.clone(),
+        models: args.models.clone(),
+        only_rules: args.only_rules.clone(),
+        disable_rules: args.disable_rules.clone(),
+    };
+    let (config_path, mut config) =
+        match config_path.and_then(|path| Config::load_with(&path, &flags).map(|config| (path, config))) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!("{} {e}", "error:".red().bold());
+                process::exit(3);
+            }
+        };
+
+    // Unlike `cacheLocation` in the config, the flag is relative to the working directory.
+    if let Some(ref location) = args.cache_location {
+        config.cache_location = Some(if location == USER_CACHE_LOCATION {
+            location.clone()
+        } else {
+            std::env::current_dir()
+                .map(|cwd| cwd.join(location))
+                .unwrap_or_else(|_| PathBuf::from(location))
+                .to_string_lossy()
+                .into_owned()
+        });
+    }
+    (config_path, config)
+}
+
+/// Apply the command-line flags that change which files are discovered.
+fn apply_discovery_flags(args: &Cli, config: &mut Config) {
+    config.ignore_patterns.extend(args.ignore.iter().cloned());
+    if args.no_ignore_files {
+        config.respect_ignore_files = false;
+    }
+}
+
+/// Open the token cache unless caching is disabled by the config or `--no-cache`.
+fn open_cache(args: &Cli, config: &Config) -> Option<TokenCache> {
+    (config.cache && !args.no_cache).then(|| TokenCache::open(&config.cache_dir(), args.cache_mode.into()))
+}
+
+fn dyn_cache(cache: &mut Option<TokenCache>) -> Option<&mut dyn Cache> {
+    cache.as_mut().map(|cache| cache as &mut dyn Cache)
+}
+
 fn run_lint(args: Cli) {
     if !args.quiet {
         println!();
         banner::print_banner();
     }
 
-    let config = match Config::load(Path::new(&args.config)) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("{} {e}", "error:".red().bold());
-            process::exit(3);
-        }
-    };
+    let (_, mut config) = load_config(&args);
+
+    apply_discovery_flags(&args, &mut config);
 
+    let source: Box<dyn SkillSource> = match args.rev {
+        Some(ref rev) => match GitSource::open(&config.root, rev) {
+            Ok(source) => {
+                if !args.quiet {
+                    println!("  {}\n", format!("Linting {rev} at {}", &source.commit()[..12]).dimmed());
+                }
+                Box::new(source)
+            }
+            Err(e) => {
+                eprintln!("{} {e}", "error:".red().bold());
+                process::exit(3);
+            }
+        },
+        None => Box::new(OsSource::new(&config.root)),
+    };
     let files = if let Some(ref file) = args.file {
-        vec![file.clone()]
+        vec![config.relativize(Path::new(file))]
     } else {
-        match lint::discover(&config) {
+        match lint::discover(&config, &*source) {
             Ok(f) => f,
             Err(e) => {
                 eprintln!("{} {e}", "error:".red().bold());
@@ -57,13 +149,16 @@ fn run_lint(args: Cli) {
         process::exit(0);
     }
 
-    let use_cache = config.cache && !args.no_cache;
-    let mut cache = if use_cache {
-        Some(TokenCache::load())
-    } else {
-        None
+    let contents = match lint::read_files(&*source, &files) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("{} {e}", "error:".red().bold());
+            process::exit(3);
+        }
     };
 
+    let mut cache = open_cache(&args, &config);
+
     let pb = ProgressBar::new(files.len() as u64);
     pb.set_style(
         ProgressStyle::with_template(
@@ -74,60 +169,49 @@ fn run_lint(args: Cli) {
     );
     pb.enable_steady_tick(std::time::Duration::from_millis(80));
 
-    let mut all_findings: Vec<LintFinding> = Vec::new();
-    let mut all_structure_findings: Vec<StructureFinding> = Vec::new();
-    for file in &files {
-        let short_name = file
-            .strip_prefix("./")
-            .unwrap_or(file);
-        pb.set_message(short_name.to_string());
-
-        match lint::lint_file(&config, file, cache.as_mut()) {
-            Ok(findings) => all_findings.extend(findings),
-            Err(e) => {
-                pb.finish_and_clear();
-                eprintln!("{} {e}", "error:".red().bold());
-                process::exit(3);
-            }
-        }
-        match lint::check_structure(&config, file) {
-            Ok(Some(sf)) => all_structure_findings.push(sf),
-            Ok(None) => {}
-            Err(e) => {
-                pb.finish_and_clear();
-                eprintln!("{} {e}", "error:".red().bold());
-                process::exit(3);
-            }
-        }
-        pb.inc(1);
+    let mut linter = Linter::new(&config)
+        .sources(files.iter().zip(&contents))
+        .aggregate_rules(args.file.is_none())
+        .on_file(|index, file| {
+            pb.set_position(index as u64);
+            pb.set_message(file.strip_prefix("./").unwrap_or(file).to_string());
+        });
+    if let Some(cache) = dyn_cache(&mut cache) {
+        linter = linter.cache(cache);
     }
-
+    let result = linter.run();
     pb.finish_and_clear();
-
-    if args.file.is_none() {
-        match skill_index_budget::check_all(&config, &files, cache.as_mut()) {
-            Ok(findings) => all_findings.extend(findings),
-            Err(e) => {
-                eprintln!("{} {e}", "error:".red().bold());
-                process::exit(3);
-            }
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{} {e}", "error:".red().bold());
+            process::exit(3);
         }
-        match unique_fields::check_all(&config, &files) {
-            Ok(findings) => all_structure_findings.extend(findings),
+    };
+
+    let costs = if args.cost {
+        match cost::estimate(&config, &files, &contents, args.file.is_none(), dyn_cache(&mut cache)) {
+            Ok(costs) => Some(costs),
             Err(e) => {
                 eprintln!("{} {e}", "error:".red().bold());
                 process::exit(3);
             }
         }
-    }
+    } else {
+        None
+    };
 
     if let Some(ref c) = cache {
-        c.flush();
+        if let Err(e) = c.flush() {
+            eprintln!("{} {e}", "warning:".yellow().bold());
+        }
     }
 
-    let report = LintReport::new(all_findings, all_structure_findings);
     println!();
     table::print_report(&report, args.verbose);
+    if let Some(ref costs) = costs {
+        cost_table::print_costs(&files, costs);
+    }
 
     let exit_code = match report.worst_severity() {
         Severity::Error => 1,
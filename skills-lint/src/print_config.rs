@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use colored::Colorize;
+use comfy_table::modifiers::UTF8_ROUND_CORNERS;
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Attribute, Cell, CellAlignment, Color, ContentArrangement, Table};
+use serde_json::{json, Value};
+use skills_lint_core::config::effective::{effective_rules, EffectiveRule};
+use skills_lint_core::config::Config;
+
+use crate::cli::OutputFormat;
+use crate::table::format_number;
+
+/// Print the rules that apply to `file` after merging extends and applying overrides.
+pub fn run(config: &Config, config_path: &Path, file: &str, format: OutputFormat) {
+    let file = config.relativize(Path::new(file));
+    let rules = effective_rules(config, &file);
+
+    match format {
+        OutputFormat::Table => print_table(config, config_path, &file, &rules),
+        OutputFormat::Json => {
+            let output = json!({
+                "file": file,
+                "config": config_path.display().to_string(),
+                "rules": rules.iter().map(|rule| rule_json(config, rule)).collect::<Vec<_>>(),
+            });
+            println!("{}", serde_json::to_string_pretty(&output).expect("failed to serialize config"));
+        }
+    }
+}
+
+fn rule_json(config: &Config, rule: &EffectiveRule) -> Value {
+    let mut value = serde_json::to_value(rule).expect("failed to serialize rule");
+    if let Some(index) = rule.override_index {
+        value["override"] = json!({
+            "index": index,
+            "files": config.overrides[index].files,
+        });
+    }
+    value
+}
+
+/// Describe where a budget came from: the global rule config or an override entry.
+fn source(config: &Config, rule: &EffectiveRule) -> String {
+    match rule.override_index {
+        Some(index) => format!("overrides[{index}] ({})", config.overrides[index].files.join(", ")),
+        None if rule.model.is_some() => "global".to_string(),
+        None => "enabled".to_string(),
+    }
+}
+
+fn print_table(config: &Config, config_path: &Path, file: &str, rules: &[EffectiveRule]) {
+    println!();
+    println!("  {}", file.bold());
+    println!("  {}", format!("config: {}", config_path.display()).dimmed());
+    println!();
+
+    if rules.is_empty() {
+        println!("  {}", "No rules apply to this file.".dimmed());
+        return;
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL_CONDENSED)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Rule").add_attribute(Attribute::Bold),
+            Cell::new("Model").add_attribute(Attribute::Bold),
+            Cell::new("Encoding").add_attribute(Attribute::Bold),
+            Cell::new("Warning")
+                .set_alignment(CellAlignment::Right)
+                .add_attribute(Attribute::Bold),
+            Cell::new("Error")
+                .set_alignment(CellAlignment::Right)
+                .add_attribute(Attribute::Bold),
+            Cell::new("Source").add_attribute(Attribute::Bold),
+        ]);
+
+    let optional = |value: Option<usize>| value.map(format_number).unwrap_or_else(|| "—".to_string());
+    for rule in rules {
+        let source_cell = match rule.override_index {
+            Some(_) => Cell::new(source(config, rule)).fg(Color::Yellow),
+            None => Cell::new(source(config, rule)).fg(Color::DarkGrey),
+        };
+        table.add_row(vec![
+            Cell::new(rule.rule),
+            Cell::new(rule.model.as_deref().unwrap_or("—")),
+            Cell::new(rule.encoding.as_deref().unwrap_or("—")),
+            Cell::new(optional(rule.warning)).set_alignment(CellAlignment::Right),
+            Cell::new(optional(rule.error)).set_alignment(CellAlignment::Right),
+            source_cell,
+        ]);
+    }
+
+    for line in table.to_string().lines() {
+        println!("  {line}");
+    }
+    println!();
+}
@@ -2,10 +2,11 @@ use colored::Colorize;
 use comfy_table::modifiers::UTF8_ROUND_CORNERS;
 use comfy_table::presets::UTF8_FULL_CONDENSED;
 use comfy_table::{Attribute, Cell, CellAlignment, Color, ContentArrangement, Table};
+use skills_lint_core::rules::size_limit;
 use skills_lint_core::rules::skill_index_budget::AGGREGATE_LABEL;
-use skills_lint_core::types::{LintReport, Severity};
+use skills_lint_core::types::{LintFinding, LintReport, Severity};
 
-fn format_number(n: usize) -> String {
+pub(crate) fn format_number(n: usize) -> String {
     let s = n.to_string();
     let mut result = String::new();
     for (i, ch) in s.chars().rev().enumerate() {
@@ -50,16 +51,22 @@ fn colored_status(severity: Severity) -> String {
     }
 }
 
-/// Build a token-limit sub-table (no File column) for a set of findings.
-fn build_token_table(findings: &[&skills_lint_core::types::LintFinding]) -> Table {
+/// Rules reported as a sub-table per file, in display order.
+const SUBTABLE_RULES: &[&str] = &["frontmatter-limit", size_limit::RULE, "token-limit"];
+
+/// Build a budget sub-table (no File column) for a set of findings.
+///
+/// `label` and `measure` name the first two columns: the model and token count, or the
+/// size metric and measured size.
+fn build_token_table(findings: &[&LintFinding], label: &str, measure: &str) -> Table {
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL_CONDENSED)
         .apply_modifier(UTF8_ROUND_CORNERS)
         .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
-            Cell::new("Model").add_attribute(Attribute::Bold),
-            Cell::new("Tokens")
+            Cell::new(label).add_attribute(Attribute::Bold),
+            Cell::new(measure)
                 .set_alignment(CellAlignment::Right)
                 .add_attribute(Attribute::Bold),
             Cell::new("Warning")
@@ -169,38 +176,21 @@ pub fn print_report(report: &LintReport, verbose: bool) {
             .filter(|f| f.file == *file_path)
             .collect();
 
-        // Partition findings by rule.
-        let frontmatter_findings: Vec<&_> = report
-            .findings
-            .iter()
-            .filter(|f| f.file == *file_path && f.rule == "frontmatter-limit")
-            .collect();
-        let token_findings: Vec<&_> = report
-            .findings
+        // Partition findings by sub-table rule. In non-verbose mode, only show non-pass rows.
+        let subtables: Vec<(&str, Vec<&LintFinding>)> = SUBTABLE_RULES
             .iter()
-            .filter(|f| f.file == *file_path && f.rule == "token-limit")
+            .map(|&rule| {
+                let findings: Vec<&LintFinding> = report
+                    .findings
+                    .iter()
+                    .filter(|f| f.file == *file_path && f.rule == rule)
+                    .filter(|f| verbose || is_notable(f.severity))
+                    .collect();
+                (rule, findings)
+            })
+            .filter(|(_, findings)| !findings.is_empty())
             .collect();
 
-        // In non-verbose mode, only show non-pass rows.
-        let visible_frontmatter_findings: Vec<&_> = if verbose {
-            frontmatter_findings.clone()
-        } else {
-            frontmatter_findings
-                .iter()
-                .filter(|f| is_notable(f.severity))
-                .copied()
-                .collect()
-        };
-        let visible_token_findings: Vec<&_> = if verbose {
-            token_findings.clone()
-        } else {
-            token_findings
-                .iter()
-                .filter(|f| is_notable(f.severity))
-                .copied()
-                .collect()
-        };
-
         let visible_structure: Vec<&_> = if verbose {
             structure_findings.clone()
         } else {
@@ -210,11 +200,9 @@ pub fn print_report(report: &LintReport, verbose: bool) {
                 .copied()
                 .collect()
         };
-        let has_visible_frontmatter = !visible_frontmatter_findings.is_empty();
-        let has_visible_tokens = !visible_token_findings.is_empty();
 
         // Structure findings (inline rules).
-        let has_subtable = has_visible_frontmatter || has_visible_tokens;
+        let has_subtable = !subtables.is_empty();
         for (i, sf) in visible_structure.iter().enumerate() {
             let is_last_inline = i + 1 == visible_structure.len();
             let is_last = is_last_inline && !has_subtable;
@@ -231,11 +219,11 @@ pub fn print_report(report: &LintReport, verbose: bool) {
             println!("  {}", "│".dimmed());
         }
 
-        // Frontmatter-limit findings (sub-table rule).
-        if has_visible_frontmatter {
-            let is_last = !has_visible_tokens;
+        // Budget findings (sub-table rules).
+        for (i, (rule, findings)) in subtables.iter().enumerate() {
+            let is_last = i + 1 == subtables.len();
             let connector = if is_last { "└─" } else { "├─" };
-            let worst = visible_frontmatter_findings
+            let worst = findings
                 .iter()
                 .map(|f| f.severity)
                 .max()
@@ -243,10 +231,14 @@ pub fn print_report(report: &LintReport, verbose: bool) {
             println!(
                 "  {} {}",
                 connector.dimmed(),
-                colored_rule_name("frontmatter-limit", worst),
+                colored_rule_name(rule, worst),
             );
 
-            let table = build_token_table(&visible_frontmatter_findings);
+            let table = if *rule == size_limit::RULE {
+                build_token_table(findings, "Metric", "Size")
+            } else {
+                build_token_table(findings, "Model", "Tokens")
+            };
             for line in table.to_string().lines() {
                 println!("     {line}");
             }
@@ -256,25 +248,6 @@ pub fn print_report(report: &LintReport, verbose: bool) {
             }
         }
 
-        // Token-limit findings (sub-table rule).
-        if has_visible_tokens {
-            let worst = visible_token_findings
-                .iter()
-                .map(|f| f.severity)
-                .max()
-                .unwrap_or(Severity::Pass);
-            println!(
-                "  {} {}",
-                "└─".dimmed(),
-                colored_rule_name("token-limit", worst),
-            );
-
-            let table = build_token_table(&visible_token_findings);
-            for line in table.to_string().lines() {
-                println!("     {line}");
-            }
-        }
-
         section_idx += 1;
         println!();
         if section_idx < total_sections {
@@ -313,7 +286,7 @@ pub fn print_report(report: &LintReport, verbose: bool) {
             colored_rule_name("skill-index-budget", worst),
         );
 
-        let table = build_token_table(&visible_aggregate);
+        let table = build_token_table(&visible_aggregate, "Model", "Tokens");
         for line in table.to_string().lines() {
             println!("     {line}");
         }
@@ -29,6 +29,7 @@ export default defineConfig({
             { text: 'Rules', link: '/guide/rules' },
             { text: 'Caching', link: '/guide/caching' },
             { text: 'CI Integration', link: '/guide/ci-integration' },
+            { text: 'Library Usage', link: '/guide/library' },
           ],
         },
       ],